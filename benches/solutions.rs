@@ -0,0 +1,32 @@
+use aoc_2022::{
+    gen,
+    solution::{self, Part},
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+fn bench_days(c: &mut Criterion) {
+    for day in &solution::DAYS {
+        let generator = gen::generator(day.day).expect("every day has a generator");
+        let input = generator.generate(2022, generator.bench_size).input;
+        let mut group = c.benchmark_group(format!("day{:02}", day.day));
+
+        group.bench_function("parse", |b| b.iter(|| day.parse(black_box(&input))));
+
+        let parsed = day.parse(&input).expect("synthetic input is valid");
+        for part in Part::ALL {
+            group.bench_function(format!("part{part}"), |b| {
+                b.iter(|| day.solve(black_box(&parsed), part))
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = bench_days
+}
+criterion_main!(benches);
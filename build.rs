@@ -0,0 +1,93 @@
+//! Generates one test per part of every puzzle example in `fixtures/`, see
+//! `src/fixtures.rs`.
+
+use std::{fmt::Write, fs, path::Path};
+
+fn expected(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// The tests of the examples in the fixture directory `dir` of one day.
+fn day_tests(dir: &Path) -> Result<String, String> {
+    let name = dir.file_name().unwrap_or_default().to_string_lossy();
+    let day: u8 = name
+        .strip_prefix("day")
+        .filter(|day| !day.is_empty() && day.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|day| day.parse().ok())
+        .ok_or_else(|| format!("{}: expected a `dayNN` directory", dir.display()))?;
+
+    let expected_path = dir.join("expected.toml");
+    let table: toml::Table = fs::read_to_string(&expected_path)
+        .map_err(|err| err.to_string())
+        .and_then(|s| s.parse().map_err(|err: toml::de::Error| err.to_string()))
+        .map_err(|err| format!("{}: {err}", expected_path.display()))?;
+
+    let mut examples = fs::read_dir(dir)
+        .and_then(|files| {
+            files
+                .map(|file| Ok(file?.path()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(|err| format!("{}: {err}", dir.display()))?;
+    examples.retain(|file| file.extension().is_some_and(|ext| ext == "txt"));
+    examples.sort();
+
+    let mut tests = String::new();
+    for example in examples {
+        // the stem ends up in the test's name
+        let stem = example.file_stem().unwrap_or_default().to_string_lossy();
+        if !stem
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+        {
+            return Err(format!(
+                "{}: example names may only contain a-z, 0-9 and _",
+                example.display()
+            ));
+        }
+        let Some(parts) = table.get(stem.as_ref()).and_then(|t| t.as_table()) else {
+            return Err(format!("{}: no `[{stem}]` table", expected_path.display()));
+        };
+        for (key, part) in [("part1", "One"), ("part2", "Two")] {
+            let Some(value) = parts.get(key) else {
+                continue;
+            };
+            writeln!(
+                tests,
+                "#[test]\nfn {name}_{stem}_{key}() {{\n    check({day}, include_str!({:?}), Part::{part}, {:?});\n}}\n",
+                example.display(),
+                expected(value),
+            )
+            .unwrap();
+        }
+    }
+    Ok(tests)
+}
+
+fn main() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+    println!("cargo::rerun-if-changed={}", fixtures.display());
+
+    let mut days = fs::read_dir(&fixtures)
+        .map(|dirs| {
+            dirs.filter_map(|dir| Some(dir.ok()?.path()))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    days.sort();
+
+    let mut tests = String::new();
+    for dir in days.iter().filter(|dir| dir.is_dir()) {
+        match day_tests(dir) {
+            Ok(day) => tests += &day,
+            // panicking would break every build, not just the tests
+            Err(err) => writeln!(tests, "compile_error!({err:?});").unwrap(),
+        }
+    }
+
+    let out = Path::new(&std::env::var("OUT_DIR").unwrap()).join("fixtures.rs");
+    fs::write(out, tests).unwrap();
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let day = aoc_2022::solution::day(5).unwrap();
+    let _ = day.parse(input);
+});
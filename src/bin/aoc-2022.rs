@@ -0,0 +1,453 @@
+use aoc_2022::{
+    gen,
+    inputs::{self, Inputs},
+    memory,
+    report::{Record, Report},
+    runner, scaffold,
+    solution::{self, Answer, Day, Part},
+    stream,
+    verify::{self, AnswerDb, Outcome},
+    viz,
+};
+use clap::{Args, Parser, Subcommand};
+use std::{
+    io::{BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+/// Advent of Code 2022 solutions, without cargo-aoc.
+#[derive(Debug, Parser)]
+#[command(name = "aoc-2022")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve one day, or all of them, and print answers with timings
+    Run(RunArgs),
+    /// Re-run every day and compare with the known answers
+    Verify(VerifyArgs),
+    /// Print a synthetic puzzle input, known answers go to stderr
+    Gen(GenArgs),
+    /// Play a day's simulation in the terminal (days 5, 9, 10, 12, 14)
+    Animate(AnimateArgs),
+    /// Create and register the module, fixtures and fuzz target of a new day
+    NewDay(NewDayArgs),
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// Day to solve
+    #[arg(long, required_unless_present = "all")]
+    day: Option<u8>,
+
+    /// Part to solve, both if omitted
+    #[arg(long, requires = "day", value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input, `-` for stdin [default: <INPUT_DIR>/dayNN.txt]
+    #[arg(long, requires = "day")]
+    input: Option<PathBuf>,
+
+    /// Solve every day concurrently and print a summary table
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+
+    /// Read the input line by line instead of all at once (days 1, 6, 9, 10)
+    #[arg(long, requires = "day", conflicts_with = "part")]
+    stream: bool,
+
+    /// Print one JSON record per day and part instead of text
+    #[arg(long, conflicts_with = "stream")]
+    json: bool,
+
+    /// Directory caching the puzzle inputs as `dayNN.txt`, missing ones are
+    /// downloaded with the session token in AOC_SESSION
+    #[arg(long, default_value = inputs::DEFAULT_DIR)]
+    input_dir: PathBuf,
+
+    /// Write every simulation step as JSON lines to this file
+    #[cfg(feature = "trace")]
+    #[arg(long)]
+    trace: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct VerifyArgs {
+    /// Database of known-correct answers
+    #[arg(long, default_value = "answers.toml")]
+    answers: PathBuf,
+
+    /// Directory containing the puzzle inputs as `dayNN.txt`
+    #[arg(long, default_value = inputs::DEFAULT_DIR)]
+    input_dir: PathBuf,
+
+    /// Add the answers for unknown inputs to the database instead of failing
+    #[arg(long)]
+    record: bool,
+}
+
+#[derive(Debug, Args)]
+struct GenArgs {
+    /// Day to generate an input for
+    #[arg(long)]
+    day: u8,
+
+    /// Seed, the same seed always produces the same input
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Scale of the input, e.g. number of elves or side length of a grid
+    #[arg(long, default_value_t = 100)]
+    size: usize,
+}
+
+#[derive(Debug, Args)]
+struct AnimateArgs {
+    /// Day to animate
+    #[arg(long)]
+    day: u8,
+
+    /// Part whose simulation to show
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Puzzle input, `-` for stdin [default: <INPUT_DIR>/dayNN.txt]
+    #[arg(long)]
+    input: Option<PathBuf>,
+
+    /// Directory caching the puzzle inputs as `dayNN.txt`, missing ones are
+    /// downloaded with the session token in AOC_SESSION
+    #[arg(long, default_value = inputs::DEFAULT_DIR)]
+    input_dir: PathBuf,
+
+    /// Frames per second
+    #[arg(long, default_value_t = 10.0)]
+    fps: f64,
+
+    /// Write the frames as text files to this directory instead of playing them
+    #[arg(long)]
+    dump: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct NewDayArgs {
+    /// Day to add
+    day: u8,
+
+    /// Root of the crate to add it to
+    #[arg(long, default_value = ".")]
+    root: PathBuf,
+}
+
+/// Reads `path`, `-` being stdin, or without one the input of `day` from
+/// `inputs`, which downloads it if need be.
+fn read_input(path: Option<&Path>, inputs: &Inputs, day: u8) -> anyhow::Result<String> {
+    match path {
+        Some(path) if path == Path::new("-") => {
+            let mut input = String::new();
+            std::io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|err| anyhow::anyhow!("cannot read {}: {err}", path.display())),
+        None => inputs.get(day),
+    }
+}
+
+fn print_answer(day: &Day, part: Part, answer: Option<Answer>, elapsed: Duration) {
+    let day = day.day;
+    match answer {
+        Some(Answer::Text(text)) => println!("day {day:02} part {part} ({elapsed:?}):\n{text}"),
+        Some(answer) => println!("day {day:02} part {part}: {answer} ({elapsed:?})"),
+        None => println!("day {day:02} part {part}: no answer ({elapsed:?})"),
+    }
+}
+
+fn print_report(report: &Report) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(report).expect("report is valid JSON")
+    );
+}
+
+fn run_all(args: RunArgs) -> ExitCode {
+    let mut failed = false;
+    let mut inputs = vec![];
+    let mut unreadable = vec![];
+    let resolver = Inputs::from_env(&args.input_dir);
+    for day in &solution::DAYS {
+        match resolver.get(day.day) {
+            Ok(input) => inputs.push((day, input)),
+            Err(err) => {
+                eprintln!("day {:02}: {err}", day.day);
+                unreadable.extend(Part::ALL.map(|part| Record::error(day.day, part, &err)));
+                failed = true;
+            }
+        }
+    }
+
+    let summary = runner::run_all(&inputs);
+    for err in &summary.errors {
+        eprintln!("{err}");
+        failed = true;
+    }
+    if args.json {
+        let mut report = summary.report();
+        report.results.extend(unreadable);
+        report.results.sort_by_key(|r| (r.day, r.part));
+        print_report(&report);
+    } else {
+        print!("{summary}");
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_stream(day: u8, path: &Path) -> anyhow::Result<()> {
+    let reader: Box<dyn BufRead> = if path == Path::new("-") {
+        Box::new(std::io::stdin().lock())
+    } else {
+        let file = std::fs::File::open(path)
+            .map_err(|err| anyhow::anyhow!("cannot read {}: {err}", path.display()))?;
+        Box::new(BufReader::new(file))
+    };
+
+    let start = Instant::now();
+    let Some(answers) = stream::solve(day, reader) else {
+        anyhow::bail!("day {day} cannot be streamed");
+    };
+    let elapsed = start.elapsed();
+    let day = solution::day(day).expect("every streamed day has a solution");
+    for (part, answer) in Part::ALL.into_iter().zip(answers?) {
+        print_answer(day, part, answer, elapsed);
+    }
+    Ok(())
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    #[cfg(feature = "trace")]
+    if let Some(path) = &args.trace {
+        if let Err(err) = aoc_2022::trace::to_file(path) {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    }
+    if args.all {
+        return run_all(args);
+    }
+    let day = args.day.expect("clap requires --day without --all");
+    let inputs = Inputs::from_env(&args.input_dir);
+    if args.stream {
+        let path = match args.input {
+            Some(path) => Ok(path),
+            // only existing days are worth downloading
+            None if stream::DAYS.contains(&day) => inputs.fetch(day),
+            None => Ok(inputs.path(day)),
+        };
+        return match path.and_then(|path| run_stream(day, &path)) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("{err}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    let Some(day) = solution::day(day) else {
+        eprintln!("there is no solution for day {day}");
+        return ExitCode::FAILURE;
+    };
+    let parts = match args.part.map(Part::try_from) {
+        Some(Ok(part)) => vec![part],
+        _ => Part::ALL.to_vec(),
+    };
+
+    let fail = |err: &dyn std::fmt::Display| {
+        if args.json {
+            let records = parts.iter().map(|&p| Record::error(day.day, p, err));
+            print_report(&Report::new(records.collect(), Duration::ZERO));
+        } else {
+            eprintln!("{err}");
+        }
+        ExitCode::FAILURE
+    };
+
+    let input = match read_input(args.input.as_deref(), &inputs, day.day) {
+        Ok(input) => input,
+        Err(err) => return fail(&format_args!("day {:02}: {err}", day.day)),
+    };
+
+    let start = Instant::now();
+    let (parsed, parse_memory) = memory::measure(|| day.parse(&input));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => return fail(&err),
+    };
+    let parse_time = start.elapsed();
+    if !args.json {
+        println!("day {:02} parse: {parse_time:?}", day.day);
+        if let Some(stats) = parse_memory {
+            println!("day {:02} parse: {stats}", day.day);
+        }
+    }
+
+    let mut records = vec![];
+    for &part in &parts {
+        let solve_start = Instant::now();
+        let (answer, solve_memory) = memory::measure(|| day.solve(&parsed, part));
+        let solve_time = solve_start.elapsed();
+        if args.json {
+            let mut record = Record::answer(day.day, part, answer.as_ref(), parse_time, solve_time);
+            record.parse_memory = parse_memory;
+            record.solve_memory = solve_memory;
+            records.push(record);
+        } else {
+            print_answer(day, part, answer, solve_time);
+            if let Some(stats) = solve_memory {
+                println!("day {:02} part {part}: {stats}", day.day);
+            }
+        }
+    }
+    if args.json {
+        print_report(&Report::new(records, start.elapsed()));
+    }
+    ExitCode::SUCCESS
+}
+
+fn verify(args: VerifyArgs) -> anyhow::Result<ExitCode> {
+    let mut db = AnswerDb::load(&args.answers)?;
+    let mut failed = false;
+    let mut recorded = false;
+
+    // verifying never downloads, it only checks the inputs at hand
+    let inputs = Inputs::from_env(&args.input_dir);
+    for day in &solution::DAYS {
+        let path = inputs.path(day.day);
+        if !path.exists() {
+            println!(
+                "day {:02}: skipped, no input at {}",
+                day.day,
+                path.display()
+            );
+            continue;
+        }
+        let input = inputs.get(day.day)?;
+        let reports = match verify::verify(&db, day, &input) {
+            Ok(reports) => reports,
+            Err(err) => {
+                eprintln!("{err}");
+                failed = true;
+                continue;
+            }
+        };
+
+        for report in reports {
+            let prefix = format!("day {:02} part {}", report.day, report.part);
+            match report.outcome {
+                Outcome::Match => println!("{prefix}: ok"),
+                Outcome::Mismatch { expected, actual } => {
+                    let actual = actual.map(|a| a.to_string()).unwrap_or_default();
+                    println!("{prefix}: MISMATCH");
+                    print!("{}", verify::diff(&expected, &actual));
+                    failed = true;
+                }
+                Outcome::Unknown {
+                    actual: Some(answer),
+                } if args.record => {
+                    println!("{prefix}: recorded {answer} for input {}", report.input);
+                    db.record(report.day, report.part, &report.input, &answer);
+                    recorded = true;
+                }
+                Outcome::Unknown { .. } => {
+                    println!("{prefix}: UNKNOWN input {}", report.input);
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    if recorded {
+        db.save(&args.answers)?;
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+fn generate(args: GenArgs) -> ExitCode {
+    let Some(generated) = gen::generate(args.day, args.seed, args.size) else {
+        eprintln!("there is no generator for day {}", args.day);
+        return ExitCode::FAILURE;
+    };
+
+    print!("{}", generated.input);
+    for (part, answer) in Part::ALL
+        .into_iter()
+        .zip([generated.part1, generated.part2])
+    {
+        if let Some(answer) = answer {
+            eprintln!("part {part}: {answer}");
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn animate(args: AnimateArgs) -> anyhow::Result<ExitCode> {
+    if !viz::DAYS.contains(&args.day) {
+        anyhow::bail!("day {} cannot be animated", args.day);
+    }
+    let inputs = Inputs::from_env(&args.input_dir);
+    let input = read_input(args.input.as_deref(), &inputs, args.day)?;
+    let part = Part::try_from(args.part).expect("clap only accepts parts 1 and 2");
+    let frames = viz::frames(args.day, part, &input).expect("day can be animated")?;
+
+    match args.dump {
+        Some(dir) => {
+            let count = viz::dump(frames, &dir)?;
+            println!("wrote {count} frames to {}", dir.display());
+        }
+        None => {
+            let delay = Duration::try_from_secs_f64(1.0 / args.fps)
+                .map_err(|_| anyhow::anyhow!("--fps must be positive"))?;
+            viz::play(frames, delay, &mut std::io::stdout().lock())?;
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn new_day(args: NewDayArgs) -> anyhow::Result<ExitCode> {
+    for path in scaffold::new_day(&args.root, args.day)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Run(args) => run(args),
+        Command::Gen(args) => generate(args),
+        Command::Animate(args) => animate(args).unwrap_or_else(|err| {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }),
+        Command::NewDay(args) => new_day(args).unwrap_or_else(|err| {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }),
+        Command::Verify(args) => verify(args).unwrap_or_else(|err| {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }),
+    }
+}
@@ -1,4 +1,8 @@
+use crate::error::ParseError;
+use crate::input::{self, normalize};
+use crate::solution::{Answer, Solution};
 use aoc_runner_derive::{aoc, aoc_generator};
+use std::io::BufRead;
 
 #[derive(Debug, Clone)]
 pub struct Elf {
@@ -6,12 +10,21 @@ pub struct Elf {
 }
 
 #[aoc_generator(day1)]
-pub fn input_gen(input: &str) -> Vec<Elf> {
-    input
-        .split("\n\n")
-        .map(|e| e.lines().map(|c| c.parse::<usize>().unwrap()).collect())
-        .map(|c| Elf { calories: c })
-        .collect()
+pub fn input_gen(input: &str) -> Result<Vec<Elf>, ParseError> {
+    let input = normalize(input);
+    let mut elves = vec![Elf { calories: vec![] }];
+    for (idx, line) in input.lines().enumerate() {
+        if line.is_empty() {
+            elves.push(Elf { calories: vec![] });
+            continue;
+        }
+
+        let calories = line
+            .parse()
+            .map_err(|_| ParseError::line(1, idx, line, "expected a calorie count"))?;
+        elves.last_mut().unwrap().calories.push(calories);
+    }
+    Ok(elves)
 }
 
 #[aoc(day1, part1)]
@@ -30,3 +43,99 @@ pub fn solve_part2(input: &[Elf]) -> usize {
         .take(3)
         .sum()
 }
+
+/// Solves both parts in a single pass over `reader`, keeping only the three
+/// largest totals in memory.
+pub fn solve_stream<R: BufRead>(reader: R) -> anyhow::Result<(usize, usize)> {
+    let mut top = [0; 3];
+    let mut current = 0;
+    let mut finish = |total: usize| {
+        if total > top[2] {
+            top[2] = total;
+            top.sort_unstable_by(|a, b| b.cmp(a));
+        }
+    };
+
+    for line in input::lines(reader) {
+        let (idx, line) = line?;
+        if line.is_empty() {
+            finish(std::mem::take(&mut current));
+            continue;
+        }
+        current += line
+            .parse::<usize>()
+            .map_err(|_| ParseError::line(1, idx, &line, "expected a calorie count"))?;
+    }
+    finish(current);
+
+    Ok((top[0], top.iter().sum()))
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input = Vec<Elf>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{input_gen, solve_part1, solve_part2, solve_stream};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
+
+    const INPUT: &str = include_str!("../fixtures/day01/example_1.txt");
+
+    #[test]
+    fn test_part1() -> anyhow::Result<()> {
+        let parsed = input_gen(INPUT)?;
+        assert_eq!(solve_part1(&parsed), Some(24000));
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> anyhow::Result<()> {
+        let parsed = input_gen(INPUT)?;
+        assert_eq!(solve_part2(&parsed), 45000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("1000\n\n20x0").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(1, 3, 1, "20x0", "expected a calorie count")
+        );
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), Some(24000));
+        assert_eq!(solve_part2(&parsed), 45000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream() -> anyhow::Result<()> {
+        assert_eq!(solve_stream(INPUT.as_bytes())?, (24000, 45000));
+        assert_eq!(
+            solve_stream(windows_style(INPUT).as_bytes())?,
+            (24000, 45000)
+        );
+        Ok(())
+    }
+}
@@ -1,3 +1,6 @@
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -60,25 +63,38 @@ impl Symbol {
     }
 }
 
+fn parse_line<'a, T>(
+    idx: usize,
+    line: &'a str,
+    second: impl Fn(&'a str) -> Option<T>,
+) -> Result<(Symbol, T), ParseError> {
+    let (first, rest) = line
+        .split_once(' ')
+        .ok_or_else(|| ParseError::line(2, idx, line, "expected two columns"))?;
+    let other = match first {
+        "A" => Symbol::Rock,
+        "B" => Symbol::Paper,
+        "C" => Symbol::Scissors,
+        _ => return Err(ParseError::at(2, idx, line, first, "expected A, B or C")),
+    };
+    let we =
+        second(rest).ok_or_else(|| ParseError::at(2, idx, line, rest, "expected X, Y or Z"))?;
+    Ok((other, we))
+}
+
 #[aoc_generator(day2, part1)]
-pub fn input_gen_part1(input: &str) -> Vec<(Symbol, Symbol)> {
+pub fn input_gen_part1(input: &str) -> Result<Vec<(Symbol, Symbol)>, ParseError> {
+    let input = normalize(input);
     input
         .lines()
-        .map(|e| {
-            let (first, second) = e.split_once(' ').unwrap();
-            let other = match first {
-                "A" => Symbol::Rock,
-                "B" => Symbol::Paper,
-                "C" => Symbol::Scissors,
-                _ => unreachable!(),
-            };
-            let we = match second {
-                "X" => Symbol::Rock,
-                "Y" => Symbol::Paper,
-                "Z" => Symbol::Scissors,
-                _ => unreachable!(),
-            };
-            (other, we)
+        .enumerate()
+        .map(|(idx, line)| {
+            parse_line(idx, line, |second| match second {
+                "X" => Some(Symbol::Rock),
+                "Y" => Some(Symbol::Paper),
+                "Z" => Some(Symbol::Scissors),
+                _ => None,
+            })
         })
         .collect()
 }
@@ -93,24 +109,18 @@ pub fn solve_part1(input: &[(Symbol, Symbol)]) -> usize {
 }
 
 #[aoc_generator(day2, part2)]
-pub fn input_gen_part2(input: &str) -> Vec<(Symbol, Ending)> {
+pub fn input_gen_part2(input: &str) -> Result<Vec<(Symbol, Ending)>, ParseError> {
+    let input = normalize(input);
     input
         .lines()
-        .map(|e| {
-            let (first, second) = e.split_once(' ').unwrap();
-            let other = match first {
-                "A" => Symbol::Rock,
-                "B" => Symbol::Paper,
-                "C" => Symbol::Scissors,
-                _ => unreachable!(),
-            };
-            let we = match second {
-                "X" => Ending::Loss,
-                "Y" => Ending::Draw,
-                "Z" => Ending::Win,
-                _ => unreachable!(),
-            };
-            (other, we)
+        .enumerate()
+        .map(|(idx, line)| {
+            parse_line(idx, line, |second| match second {
+                "X" => Some(Ending::Loss),
+                "Y" => Some(Ending::Draw),
+                "Z" => Some(Ending::Win),
+                _ => None,
+            })
         })
         .collect()
 }
@@ -124,14 +134,36 @@ pub fn solve_part2(input: &[(Symbol, Ending)]) -> usize {
         .sum()
 }
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input = (Vec<(Symbol, Symbol)>, Vec<(Symbol, Ending)>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok((input_gen_part1(input)?, input_gen_part2(input)?))
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(&input.0).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(&input.1).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{input_gen_part1, input_gen_part2, solve_part1, solve_part2, Ending, Symbol};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
+
+    const INPUT: &str = include_str!("../fixtures/day02/example_1.txt");
 
     #[test]
-    fn test_part1() {
-        let input = "A Y\nB X\nC Z";
-        let parsed = input_gen_part1(input);
+    fn test_part1() -> anyhow::Result<()> {
+        let parsed = input_gen_part1(INPUT)?;
         assert_eq!(
             parsed,
             vec![
@@ -141,13 +173,13 @@ mod tests {
             ]
         );
         let score = solve_part1(&parsed);
-        assert_eq!(score, 15)
+        assert_eq!(score, 15);
+        Ok(())
     }
 
     #[test]
-    fn test_part2() {
-        let input = "A Y\nB X\nC Z";
-        let parsed = input_gen_part2(input);
+    fn test_part2() -> anyhow::Result<()> {
+        let parsed = input_gen_part2(INPUT)?;
         assert_eq!(
             parsed,
             vec![
@@ -157,6 +189,21 @@ mod tests {
             ]
         );
         let score = solve_part2(&parsed);
-        assert_eq!(score, 12)
+        assert_eq!(score, 12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen_part1("A Y\nB W").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 2, 3, "W", "expected X, Y or Z"));
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let input = windows_style(INPUT);
+        assert_eq!(solve_part1(&input_gen_part1(&input)?), 15);
+        assert_eq!(solve_part2(&input_gen_part2(&input)?), 12);
+        Ok(())
     }
 }
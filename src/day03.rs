@@ -1,3 +1,6 @@
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 type Item = char;
@@ -16,16 +19,36 @@ pub struct Groups {
 }
 
 #[aoc_generator(day3, part1)]
-pub fn input_gen_part1(input: &str) -> Vec<Rucksack> {
+pub fn input_gen_part1(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    let input = normalize(input);
     input
         .lines()
-        .map(|e| {
+        .enumerate()
+        .map(|(idx, e)| {
+            if let Some((pos, c)) = e.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                return Err(ParseError::at(
+                    3,
+                    idx,
+                    e,
+                    &e[pos..pos + c.len_utf8()],
+                    "expected an item (a-z, A-Z)",
+                ));
+            }
+            if e.len() % 2 != 0 {
+                return Err(ParseError::line(
+                    3,
+                    idx,
+                    e,
+                    "expected an even number of items",
+                ));
+            }
+
             let (c1, c2) = e.split_at(e.len() / 2);
-            Rucksack {
+            Ok(Rucksack {
                 items: e.chars().collect(),
                 compartment_1: c1.chars().collect(),
                 compartment_2: c2.chars().collect(),
-            }
+            })
         })
         .collect()
 }
@@ -49,15 +72,27 @@ pub fn solve_part1(input: &[Rucksack]) -> u32 {
 }
 
 #[aoc_generator(day3, part2)]
-pub fn input_gen_part2(input: &str) -> Vec<Groups> {
-    let bags = input_gen_part1(input);
-    bags.chunks_exact(3)
+pub fn input_gen_part2(input: &str) -> Result<Vec<Groups>, ParseError> {
+    let input = normalize(input);
+    let bags = input_gen_part1(&input)?;
+    if bags.len() % 3 != 0 {
+        let (idx, line) = input.lines().enumerate().last().unwrap_or((0, ""));
+        return Err(ParseError::line(
+            3,
+            idx,
+            line,
+            "expected the number of rucksacks to be a multiple of three",
+        ));
+    }
+
+    Ok(bags
+        .chunks_exact(3)
         .map(|group| Groups {
             bag_1: group[0].items.clone(),
             bag_2: group[1].items.clone(),
             bag_3: group[2].items.clone(),
         })
-        .collect()
+        .collect())
 }
 
 #[aoc(day3, part2)]
@@ -77,23 +112,63 @@ pub fn solve_part2(input: &[Groups]) -> u32 {
         .sum()
 }
 
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input = (Vec<Rucksack>, Vec<Groups>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok((input_gen_part1(input)?, input_gen_part2(input)?))
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(&input.0).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(&input.1).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{input_gen_part1, input_gen_part2, solve_part1, solve_part2};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
+
+    const INPUT: &str = include_str!("../fixtures/day03/example_1.txt");
 
     #[test]
-    fn test_part1() {
-        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
-        let parsed = input_gen_part1(input);
+    fn test_part1() -> anyhow::Result<()> {
+        let parsed = input_gen_part1(INPUT)?;
         let priority = solve_part1(&parsed);
-        assert_eq!(priority, 157)
+        assert_eq!(priority, 157);
+        Ok(())
     }
 
     #[test]
-    fn test_part2() {
-        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw";
-        let parsed = input_gen_part2(input);
+    fn test_part2() -> anyhow::Result<()> {
+        let parsed = input_gen_part2(INPUT)?;
         let priority = solve_part2(&parsed);
-        assert_eq!(priority, 70)
+        assert_eq!(priority, 70);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen_part1("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRj1zjGDLGL").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(3, 2, 9, "1", "expected an item (a-z, A-Z)")
+        );
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let input = windows_style(INPUT);
+        assert_eq!(solve_part1(&input_gen_part1(&input)?), 157);
+        assert_eq!(solve_part2(&input_gen_part2(&input)?), 70);
+        Ok(())
     }
 }
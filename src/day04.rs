@@ -1,3 +1,6 @@
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::ops::RangeInclusive;
 
@@ -8,21 +11,29 @@ pub struct Pair {
 }
 
 #[aoc_generator(day4)]
-pub fn input_gen(input: &str) -> Vec<Pair> {
+pub fn input_gen(input: &str) -> Result<Vec<Pair>, ParseError> {
+    let input = normalize(input);
     input
         .lines()
-        .map(|e| {
-            let (r1, r2) = e.split_once(',').unwrap();
+        .enumerate()
+        .map(|(idx, e)| {
+            let (r1, r2) = e.split_once(',').ok_or_else(|| {
+                ParseError::line(4, idx, e, "expected two comma separated ranges")
+            })?;
+            let number = |n: &str| {
+                n.parse::<u32>()
+                    .map_err(|_| ParseError::at(4, idx, e, n, "expected a section id"))
+            };
             let range = |r: &str| {
-                r.split_once('-')
-                    .map(|(a, b)| (a.parse::<u32>().unwrap(), b.parse::<u32>().unwrap()))
-                    .map(|(a, b)| a..=b)
-                    .unwrap()
+                let (a, b) = r
+                    .split_once('-')
+                    .ok_or_else(|| ParseError::at(4, idx, e, r, "expected a range `a-b`"))?;
+                Ok::<_, ParseError>(number(a)?..=number(b)?)
             };
-            Pair {
-                range_1: range(r1),
-                range_2: range(r2),
-            }
+            Ok(Pair {
+                range_1: range(r1)?,
+                range_2: range(r2)?,
+            })
         })
         .collect()
 }
@@ -51,23 +62,64 @@ pub fn solve_part2(input: &[Pair]) -> usize {
         .count()
 }
 
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input = Vec<Pair>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{input_gen, solve_part1, solve_part2};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
+
+    const INPUT: &str = include_str!("../fixtures/day04/example_1.txt");
 
     #[test]
-    fn test_part1() {
-        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
-        let parsed = input_gen(input);
+    fn test_part1() -> anyhow::Result<()> {
+        let parsed = input_gen(INPUT)?;
         let count = solve_part1(&parsed);
-        assert_eq!(count, 2)
+        assert_eq!(count, 2);
+        Ok(())
     }
 
     #[test]
-    fn test_part2() {
-        let input = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
-        let parsed = input_gen(input);
+    fn test_part2() -> anyhow::Result<()> {
+        let parsed = input_gen(INPUT)?;
         let priority = solve_part2(&parsed);
-        assert_eq!(priority, 4)
+        assert_eq!(priority, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("2-4,6-8\n2-3,4_5").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(4, 2, 5, "4_5", "expected a range `a-b`")
+        );
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let input = windows_style(INPUT);
+        let parsed = input_gen(&input)?;
+        assert_eq!(solve_part1(&parsed), 2);
+        assert_eq!(solve_part2(&parsed), 4);
+        Ok(())
     }
 }
@@ -1,5 +1,9 @@
 use std::collections::VecDeque;
 
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Part, Solution};
+use crate::trace::{event, span};
 use aoc_runner_derive::{aoc, aoc_generator};
 use regex::Regex;
 
@@ -18,83 +22,218 @@ pub struct Puzzle {
 }
 
 #[aoc_generator(day5)]
-pub fn input_gen(input: &str) -> anyhow::Result<Puzzle> {
-    let num_stacks = (input.chars().take_while(|&c| c != '\n').count() + 1) / 4;
+pub fn input_gen(input: &str) -> Result<Puzzle, ParseError> {
+    let input = normalize(input);
+    let first_line = input.lines().next().unwrap_or_default();
+    // the stack labels below the drawing tell us how many stacks there are,
+    // the rows above may have lost their trailing empty slots to normalization
+    let num_stacks = input
+        .lines()
+        .take_while(|line| !line.is_empty())
+        .last()
+        .map_or(0, |labels| labels.split_whitespace().count());
+    if num_stacks == 0 {
+        return Err(ParseError::line(
+            5,
+            0,
+            first_line,
+            "expected a stack drawing",
+        ));
+    }
+    let width = num_stacks * 4 - 1;
+
     let re = (0..num_stacks).fold(String::new(), |mut string, _| {
         string += r"(?:\[([A-Z])\]|   )[ ]?";
         string
     });
-    let re = Regex::new(&re)?;
-    let stacks = input
-        .lines()
-        .take_while(|line| !line.starts_with("move"))
-        .filter_map(|line| re.captures(line))
-        .fold(vec![VecDeque::new(); num_stacks], |mut stacks, capture| {
-            for (i, stack) in stacks.iter_mut().enumerate() {
-                if let Some(c) = capture.get(i + 1) {
-                    stack.push_front(c.as_str().chars().next().unwrap());
+    let re = Regex::new(&re)
+        .map_err(|_| ParseError::line(5, 0, first_line, "unsupported stack drawing"))?;
+    let mut lines = input.lines().enumerate();
+    let stacks = lines
+        .by_ref()
+        .take_while(|(_, line)| !line.is_empty())
+        .filter(|(_, line)| !line.trim_start().starts_with(|c: char| c.is_ascii_digit()))
+        .try_fold(
+            vec![VecDeque::new(); num_stacks],
+            |mut stacks, (idx, line)| {
+                let row = format!("{line:<width$}");
+                let capture = re
+                    .captures(&row)
+                    .ok_or_else(|| ParseError::line(5, idx, line, "expected a row of crates"))?;
+                for (i, stack) in stacks.iter_mut().enumerate() {
+                    if let Some(c) = capture.get(i + 1) {
+                        stack.push_front(c.as_str().chars().next().unwrap());
+                    }
                 }
-            }
-            stacks
-        });
+                Ok(stacks)
+            },
+        )?;
 
-    let re = Regex::new(r"move (\d+) from (\d+) to (\d+)")?;
-    let moves: Vec<_> = input
-        .lines()
-        .skip_while(|line| !line.starts_with("move"))
-        .filter_map(|line| {
-            let captures = re.captures(line)?;
-            Some(Move {
-                count: captures[1].parse().ok()?,
-                from_id: captures[2].parse::<usize>().ok()? - 1,
-                to_id: captures[3].parse::<usize>().ok()? - 1,
-            })
+    let re = Regex::new(r"^move (\d+) from (\d+) to (\d+)$")
+        .map_err(|_| ParseError::line(5, 0, first_line, "unsupported move format"))?;
+    // the crane can't take more crates than a stack holds
+    let mut heights = stacks.iter().map(VecDeque::len).collect::<Vec<_>>();
+    let moves = lines
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| {
+            let captures = re
+                .captures(line)
+                .ok_or_else(|| ParseError::line(5, idx, line, "expected `move N from A to B`"))?;
+            let number = |i: usize| {
+                let m = captures.get(i).unwrap();
+                m.as_str()
+                    .parse::<usize>()
+                    .map_err(|_| ParseError::at(5, idx, line, m.as_str(), "number out of range"))
+            };
+            let stack_id = |i: usize| {
+                let id = number(i)?;
+                if (1..=num_stacks).contains(&id) {
+                    Ok(id - 1)
+                } else {
+                    let m = captures.get(i).unwrap();
+                    Err(ParseError::at(5, idx, line, m.as_str(), "unknown stack"))
+                }
+            };
+            let m = Move {
+                count: number(1)?,
+                from_id: stack_id(2)?,
+                to_id: stack_id(3)?,
+            };
+            if m.count > heights[m.from_id] {
+                let count = captures.get(1).unwrap().as_str();
+                let reason = format!(
+                    "stack {} holds {} crates",
+                    m.from_id + 1,
+                    heights[m.from_id]
+                );
+                return Err(ParseError::at(5, idx, line, count, reason));
+            }
+            heights[m.from_id] -= m.count;
+            heights[m.to_id] += m.count;
+            Ok(m)
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     Ok(Puzzle { stacks, moves })
 }
 
+/// The crates on top of the stacks, stacks that ended up empty have none.
+fn tops(stacks: &[VecDeque<char>]) -> String {
+    stacks.iter().filter_map(VecDeque::back).collect()
+}
+
 #[aoc(day5, part1)]
 pub fn solve_part1(input: &Puzzle) -> String {
+    let _span = span!("part1");
     let mut stacks = input.stacks.clone();
     for m in input.moves.iter() {
+        let _move = span!(
+            "move",
+            count = m.count,
+            from = m.from_id + 1,
+            to = m.to_id + 1
+        );
         for _ in 0..m.count {
-            let item = stacks[m.from_id].pop_back().unwrap();
+            let item = stacks[m.from_id].pop_back().expect("checked by the parser");
+            event!(%item, "crate");
             stacks[m.to_id].push_back(item);
         }
     }
 
-    stacks
-        .into_iter()
-        .fold(String::new(), |mut string, mut stack| {
-            string.push(stack.pop_back().unwrap());
-            string
-        })
+    tops(&stacks)
 }
 
 #[aoc(day5, part2)]
 pub fn solve_part2(input: &Puzzle) -> String {
+    let _span = span!("part2");
     let mut stacks = input.stacks.clone();
     for m in input.moves.iter() {
         let len = stacks[m.from_id].len();
         let other = stacks[m.from_id].split_off(len - m.count);
+        event!(
+            count = m.count,
+            from = m.from_id + 1,
+            to = m.to_id + 1,
+            crates = %other.iter().collect::<String>(),
+            "move"
+        );
         stacks[m.to_id].extend(other);
     }
 
-    stacks
-        .into_iter()
-        .fold(String::new(), |mut string, mut stack| {
-            string.push(stack.pop_back().unwrap());
-            string
-        })
+    tops(&stacks)
+}
+
+/// Draws the stacks the way the puzzle does, labels below.
+fn draw(stacks: &[VecDeque<char>]) -> String {
+    let height = stacks.iter().map(VecDeque::len).max().unwrap_or(0);
+    let mut drawing = String::new();
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|stack| stack.get(level).map_or("   ".into(), |c| format!("[{c}]")))
+            .collect::<Vec<_>>()
+            .join(" ");
+        drawing += row.trim_end();
+        drawing.push('\n');
+    }
+    let labels = (1..=stacks.len())
+        .map(|id| format!(" {id} "))
+        .collect::<Vec<_>>()
+        .join(" ");
+    drawing += labels.trim_end();
+    drawing.push('\n');
+    drawing
+}
+
+/// One frame per move of the crane, the CrateMover 9000 of part 1 or the
+/// 9001 of part 2, starting with the initial stacks.
+pub fn frames(puzzle: Puzzle, part: Part) -> impl Iterator<Item = String> {
+    let Puzzle { mut stacks, moves } = puzzle;
+    let start = format!("start\n\n{}", draw(&stacks));
+    std::iter::once(start).chain(moves.into_iter().map(move |m| {
+        let len = stacks[m.from_id].len();
+        let moved = stacks[m.from_id].split_off(len - m.count);
+        match part {
+            // moving crates one at a time reverses their order
+            Part::One => stacks[m.to_id].extend(moved.into_iter().rev()),
+            Part::Two => stacks[m.to_id].extend(moved),
+        }
+        format!(
+            "move {} from {} to {}\n\n{}",
+            m.count,
+            m.from_id + 1,
+            m.to_id + 1,
+            draw(&stacks)
+        )
+    }))
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Input = Puzzle;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(input).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{input_gen, solve_part1, solve_part2};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
 
-    const INPUT: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2";
+    const INPUT: &str = include_str!("../fixtures/day05/example_1.txt");
 
     #[test]
     fn test_part1() -> anyhow::Result<()> {
@@ -111,4 +250,31 @@ mod tests {
         assert_eq!(crates, "MCD");
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("move 3 from 1 to 3", "move 3 from 1 to 4");
+        let err = input_gen(&input).unwrap_err();
+        assert_eq!(err, ParseError::new(5, 7, 18, "4", "unknown stack"));
+        let input = INPUT.replace("move 1 from 1 to 2", "move 3 from 1 to 2");
+        let err = input_gen(&input).unwrap_err();
+        let reason = "stack 1 holds 2 crates";
+        assert_eq!(err, ParseError::new(5, 9, 6, "3", reason));
+    }
+
+    #[test]
+    fn test_empty_stack() -> anyhow::Result<()> {
+        let parsed = input_gen(&format!("{INPUT}move 1 from 1 to 2\n"))?;
+        assert_eq!(solve_part1(&parsed), "CZ");
+        assert_eq!(solve_part2(&parsed), "MD");
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), "CMZ");
+        assert_eq!(solve_part2(&parsed), "MCD");
+        Ok(())
+    }
 }
@@ -1,31 +1,113 @@
-use aoc_runner_derive::aoc;
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::io::BufRead;
 
-fn solve(input: &[u8], n: usize) -> usize {
+#[aoc_generator(day6)]
+pub fn input_gen(input: &str) -> Result<Vec<u8>, ParseError> {
+    let input = normalize(input);
+    let mut lines = input.lines();
+    let (Some(line), None) = (lines.next(), lines.next()) else {
+        return Err(ParseError::new(6, 1, 1, input, "expected a single line"));
+    };
+    if let Some((pos, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+        let token = &line[pos..pos + c.len_utf8()];
+        return Err(ParseError::at(6, 0, line, token, "expected a-z"));
+    }
+    Ok(line.as_bytes().to_vec())
+}
+
+/// Position right after the first `n` distinct characters, `None` if there
+/// are none.
+fn solve(input: &[u8], n: usize) -> Option<usize> {
     input
         .windows(n)
-        .take_while(|&chars| {
+        .position(|chars| {
             let flags = chars
                 .iter()
                 .fold(0u32, |flags, char| flags | (1 << (char - b'a')));
-            flags.count_ones() as usize != n
+            flags.count_ones() as usize == n
         })
-        .count()
-        + n
+        .map(|start| start + n)
 }
 
 #[aoc(day6, part1)]
-pub fn solve_part1(input: &[u8]) -> usize {
+pub fn solve_part1(input: &[u8]) -> Option<usize> {
     solve(input, 4)
 }
 
 #[aoc(day6, part2)]
-pub fn solve_part2(input: &[u8]) -> usize {
+pub fn solve_part2(input: &[u8]) -> Option<usize> {
     solve(input, 14)
 }
 
+/// Finds both markers while reading `reader` byte by byte, only the last 14
+/// characters are kept in memory.
+///
+/// Reading stops once both markers are found, a marker that never appears is
+/// `None`.
+pub fn solve_stream<R: BufRead>(mut reader: R) -> anyhow::Result<(Option<usize>, Option<usize>)> {
+    let mut window = [0u8; 14];
+    let mut markers = [(4, None), (14, None)];
+
+    if reader.fill_buf()?.starts_with("\u{feff}".as_bytes()) {
+        reader.consume(3);
+    }
+    let mut len = 0;
+    for byte in reader.bytes() {
+        let byte = byte?;
+        if byte.is_ascii_whitespace() {
+            break;
+        }
+        if !byte.is_ascii_lowercase() {
+            let text = String::from_utf8_lossy(&[byte]).into_owned();
+            return Err(ParseError::new(6, 1, len + 1, text, "expected a-z").into());
+        }
+
+        window[len % 14] = byte;
+        len += 1;
+        for (n, marker) in &mut markers {
+            if marker.is_none() && len >= *n {
+                let flags =
+                    (len - *n..len).fold(0u32, |flags, i| flags | (1 << (window[i % 14] - b'a')));
+                if flags.count_ones() as usize == *n {
+                    *marker = Some(len);
+                }
+            }
+        }
+        if markers.iter().all(|(_, marker)| marker.is_some()) {
+            break;
+        }
+    }
+
+    Ok((markers[0].1, markers[1].1))
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Input = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        solve_part2(input).map(Answer::from)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{solve_part1, solve_part2};
+    use super::{input_gen, solve_part1, solve_part2, solve_stream};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
 
     const INPUT_PART_1: [&[u8]; 5] = [
         b"mjqjpqmgbljsphdztnvjfqwrcgsmlb",
@@ -41,7 +123,7 @@ mod tests {
     fn test_part1() -> anyhow::Result<()> {
         for (input, result) in INPUT_PART_1.into_iter().zip(RESULT_PART_1) {
             let len = solve_part1(input);
-            assert_eq!(len, result);
+            assert_eq!(len, Some(result));
         }
         Ok(())
     }
@@ -50,8 +132,56 @@ mod tests {
     fn test_part2() -> anyhow::Result<()> {
         for (input, result) in INPUT_PART_1.into_iter().zip(RESULT_PART_2) {
             let len = solve_part2(input);
-            assert_eq!(len, result);
+            assert_eq!(len, Some(result));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_no_marker() -> anyhow::Result<()> {
+        for input in ["abcabc", "abc"] {
+            let parsed = input_gen(input)?;
+            let solved = (solve_part1(&parsed), solve_part2(&parsed));
+            assert_eq!(solved, solve_stream(input.as_bytes())?);
+        }
+        assert_eq!(solve_part1(b"abcabc"), None);
+        assert_eq!(solve_part2(b"abcdabcd"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("mjqjpqmg-ljsphdztnvjfqwrcgsmlb").unwrap_err();
+        assert_eq!(err, ParseError::new(6, 1, 9, "-", "expected a-z"));
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        for ((input, result_1), result_2) in INPUT_PART_1
+            .into_iter()
+            .zip(RESULT_PART_1)
+            .zip(RESULT_PART_2)
+        {
+            let parsed = input_gen(&windows_style(std::str::from_utf8(input)?))?;
+            assert_eq!(solve_part1(&parsed), Some(result_1));
+            assert_eq!(solve_part2(&parsed), Some(result_2));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream() -> anyhow::Result<()> {
+        for ((input, result_1), result_2) in INPUT_PART_1
+            .into_iter()
+            .zip(RESULT_PART_1)
+            .zip(RESULT_PART_2)
+        {
+            let expected = (Some(result_1), Some(result_2));
+            assert_eq!(solve_stream(input)?, expected);
+            let messy = windows_style(std::str::from_utf8(input)?);
+            assert_eq!(solve_stream(messy.as_bytes())?, expected);
         }
+        assert_eq!(solve_stream(&b"abcdabcd\n"[..])?, (Some(4), None));
         Ok(())
     }
 }
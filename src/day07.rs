@@ -1,23 +1,28 @@
-use aoc_runner_derive::aoc;
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
+use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::{hash_map::Values, HashMap};
 
+/// A directory of the device's file system with everything below it.
 #[derive(Debug)]
-struct Directory<'a>(HashMap<&'a str, Node<'a>>);
+pub struct Directory(HashMap<String, Node>);
 
-impl<'a> Directory<'a> {
+impl Directory {
     fn new() -> Self {
         Self(HashMap::new())
     }
 
-    fn insert(&mut self, key: &'a str, value: Node<'a>) {
-        self.0.insert(key, value);
+    fn insert(&mut self, key: &str, value: Node) {
+        self.0.insert(key.to_owned(), value);
     }
 
-    fn get_mut(&mut self, key: &'a str) -> Option<&mut Node<'a>> {
+    fn get_mut(&mut self, key: &str) -> Option<&mut Node> {
         self.0.get_mut(key)
     }
 
-    fn size(&self) -> u64 {
+    /// Total size of all files in this directory and its subdirectories.
+    pub fn size(&self) -> u64 {
         self.0
             .values()
             .map(|node| match node {
@@ -27,7 +32,8 @@ impl<'a> Directory<'a> {
             .sum()
     }
 
-    fn dir_iter(&self) -> DirIter {
+    /// All directories below this one, at any depth, in no particular order.
+    pub fn subdirectories(&self) -> impl Iterator<Item = &Directory> + '_ {
         DirIter {
             values: self.0.values(),
             childs: vec![],
@@ -36,12 +42,12 @@ impl<'a> Directory<'a> {
 }
 
 struct DirIter<'a> {
-    values: Values<'a, &'a str, Node<'a>>,
-    childs: Vec<&'a Directory<'a>>,
+    values: Values<'a, String, Node>,
+    childs: Vec<&'a Directory>,
 }
 
 impl<'a> Iterator for DirIter<'a> {
-    type Item = &'a Directory<'a>;
+    type Item = &'a Directory;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
@@ -62,83 +68,140 @@ impl<'a> Iterator for DirIter<'a> {
 }
 
 #[derive(Debug)]
-enum Node<'a> {
-    Directory(Directory<'a>),
+enum Node {
+    Directory(Directory),
     File { size: u64 },
 }
 
-fn input_gen<'a>(input: &mut &'a str, tree: &mut Directory<'a>) {
-    loop {
-        if input.is_empty() {
-            return;
-        }
-
-        let (line, rest) = input.split_once('\n').unwrap_or((input, ""));
-        *input = rest;
-
-        if line.starts_with("dir") {
-            tree.insert(
-                line.trim_start_matches("dir "),
-                Node::Directory(Directory::new()),
-            );
+fn parse_dir<'a, I>(lines: &mut I, tree: &mut Directory, is_root: bool) -> Result<(), ParseError>
+where
+    I: Iterator<Item = (usize, &'a str)>,
+{
+    while let Some((idx, line)) = lines.next() {
+        if let Some(name) = line.strip_prefix("dir ") {
+            tree.insert(name, Node::Directory(Directory::new()));
         } else if line == "$ ls" || line == "$ cd /" {
         } else if line == "$ cd .." {
-            return;
-        } else if line.starts_with("$ cd") {
-            let Some(Node::Directory(dir)) = tree.get_mut(line.trim_start_matches("$ cd ")) else {panic!()};
-            input_gen(input, dir);
+            if is_root {
+                return Err(ParseError::line(
+                    7,
+                    idx,
+                    line,
+                    "cannot leave the root directory",
+                ));
+            }
+            return Ok(());
+        } else if let Some(name) = line.strip_prefix("$ cd ") {
+            let Some(Node::Directory(dir)) = tree.get_mut(name) else {
+                return Err(ParseError::at(7, idx, line, name, "unknown directory"));
+            };
+            parse_dir(lines, dir, false)?;
+        } else if line.starts_with('$') {
+            return Err(ParseError::line(7, idx, line, "unknown command"));
         } else {
-            let (size, name) = line.split_once(' ').unwrap();
-            tree.insert(
-                name,
-                Node::File {
-                    size: size.parse().unwrap(),
-                },
-            );
+            let (size, name) = line
+                .split_once(' ')
+                .ok_or_else(|| ParseError::line(7, idx, line, "expected `<size> <name>`"))?;
+            let size = size
+                .parse()
+                .map_err(|_| ParseError::at(7, idx, line, size, "expected a file size"))?;
+            tree.insert(name, Node::File { size });
         }
     }
+    Ok(())
 }
 
-#[aoc(day7, part1)]
-pub fn solve_part1(mut input: &str) -> u64 {
+#[aoc_generator(day7)]
+pub fn input_gen(input: &str) -> Result<Directory, ParseError> {
+    let input = normalize(input);
     let mut tree = Directory::new();
-    input_gen(&mut input, &mut tree);
-    tree.dir_iter()
+    parse_dir(&mut input.lines().enumerate(), &mut tree, true)?;
+    Ok(tree)
+}
+
+#[aoc(day7, part1)]
+pub fn solve_part1(tree: &Directory) -> u64 {
+    tree.subdirectories()
         .map(|d| d.size())
         .filter(|&s| s <= 100_000)
         .sum::<u64>()
 }
 
+/// `None` if the files don't fit on the disk, or if there is enough free
+/// space already.
 #[aoc(day7, part2)]
-pub fn solve_part2(mut input: &str) -> Option<u64> {
-    let mut tree = Directory::new();
-    input_gen(&mut input, &mut tree);
+pub fn solve_part2(tree: &Directory) -> Option<u64> {
     let space_used = tree.size();
-    let free_space = 70_000_000 - space_used;
-    let space_to_free = 30_000_000 - free_space;
-    tree.dir_iter()
+    let free_space = 70_000_000u64.checked_sub(space_used)?;
+    let space_to_free = 30_000_000u64.checked_sub(free_space)?;
+    tree.subdirectories()
         .map(|d| d.size())
         .filter(|&s| s >= space_to_free)
         .min()
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Input = Directory;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        solve_part2(input).map(Answer::from)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{solve_part1, solve_part2};
+    use super::{input_gen, solve_part1, solve_part2};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
 
-    const INPUT: &str = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n$ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k";
+    const INPUT: &str = include_str!("../fixtures/day07/example_1.txt");
 
     #[test]
     fn test_part1() -> anyhow::Result<()> {
-        let size = solve_part1(INPUT);
+        let size = solve_part1(&input_gen(INPUT)?);
         assert_eq!(size, 95437);
         Ok(())
     }
 
     #[test]
     fn test_part2() -> anyhow::Result<()> {
-        let size = solve_part2(INPUT);
+        let size = solve_part2(&input_gen(INPUT)?);
         assert_eq!(size, Some(24933642));
         Ok(())
     }
+
+    #[test]
+    fn test_disk_size() -> anyhow::Result<()> {
+        let full = input_gen("$ cd /\n$ ls\n80000000 a")?;
+        assert_eq!(solve_part2(&full), None);
+        let empty = input_gen("$ cd /\n$ ls\n100 a")?;
+        assert_eq!(solve_part2(&empty), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("$ cd d", "$ cd x");
+        let err = input_gen(&input).unwrap_err();
+        assert_eq!(err, ParseError::new(7, 18, 6, "x", "unknown directory"));
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), 95437);
+        assert_eq!(solve_part2(&parsed), Some(24933642));
+        Ok(())
+    }
 }
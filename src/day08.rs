@@ -1,147 +1,115 @@
-use aoc_runner_derive::aoc;
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos, ORTHOGONAL};
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
+use aoc_runner_derive::{aoc, aoc_generator};
+
+/// Number of trees seen from `pos` when looking along `step`, and whether the
+/// view reaches the edge of the grid.
+fn view(grid: &Grid<u32>, pos: Pos, step: (isize, isize)) -> (usize, bool) {
+    let mut distance = 0;
+    for (_, &height) in grid.ray(pos, step) {
+        distance += 1;
+        if height >= grid[pos] {
+            return (distance, false);
+        }
+    }
+    (distance, true)
+}
 
-#[derive(Debug)]
-pub struct Tree {
-    height: u32,
+fn is_visible(grid: &Grid<u32>, pos: Pos) -> bool {
+    ORTHOGONAL.iter().any(|&step| view(grid, pos, step).1)
 }
 
-pub struct Grid {
-    dim_x: usize,
-    dim_y: usize,
-    content: Vec<Tree>,
+fn score(grid: &Grid<u32>, pos: Pos) -> usize {
+    ORTHOGONAL
+        .iter()
+        .map(|&step| view(grid, pos, step).0)
+        .product()
 }
 
-impl Grid {
-    fn is_border(&self, (y, x): (usize, usize)) -> bool {
-        x == 0 || y == 0 || x == self.dim_x - 1 || y == self.dim_y - 1
-    }
+#[aoc_generator(day8)]
+pub fn input_gen(input: &str) -> Result<Grid<u32>, ParseError> {
+    let input = normalize(input);
+    Grid::parse(8, &input, |_, c| c.to_digit(10).ok_or("expected a digit"))
+}
 
-    fn check_top(&self, (y, x): (usize, usize), tree: &Tree) -> Option<usize> {
-        (0..y)
-            .map(|y| y * self.dim_x + x)
-            .map(|i| &self.content[i])
-            .rev()
-            .position(|t| t.height >= tree.height)
-            .map(|p| p + 1)
-    }
+#[aoc(day8, part1)]
+pub fn solve_part1(grid: &Grid<u32>) -> usize {
+    grid.positions()
+        .filter(|&pos| is_visible(grid, pos))
+        .count()
+}
 
-    fn check_bottom(&self, (y, x): (usize, usize), tree: &Tree) -> Option<usize> {
-        (y + 1..self.dim_y)
-            .map(|y| y * self.dim_x + x)
-            .map(|i| &self.content[i])
-            .position(|t| t.height >= tree.height)
-            .map(|p| p + 1)
-    }
+#[aoc(day8, part2)]
+pub fn solve_part2(grid: &Grid<u32>) -> Option<usize> {
+    grid.positions().map(|pos| score(grid, pos)).max()
+}
 
-    fn check_left(&self, (y, x): (usize, usize), tree: &Tree) -> Option<usize> {
-        (0..x)
-            .map(|x| y * self.dim_x + x)
-            .map(|i| &self.content[i])
-            .rev()
-            .position(|t| t.height >= tree.height)
-            .map(|p| p + 1)
-    }
+pub struct Day08;
 
-    fn check_right(&self, (y, x): (usize, usize), tree: &Tree) -> Option<usize> {
-        (x + 1..self.dim_x)
-            .map(|x| y * self.dim_x + x)
-            .map(|i| &self.content[i])
-            .position(|t| t.height >= tree.height)
-            .map(|p| p + 1)
-    }
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Input = Grid<u32>;
 
-    fn is_visible(&self, pos: (usize, usize)) -> bool {
-        let tree = self.get(pos);
-        self.is_border(pos)
-            || self.check_top(pos, tree).is_none()
-            || self.check_bottom(pos, tree).is_none()
-            || self.check_left(pos, tree).is_none()
-            || self.check_right(pos, tree).is_none()
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
     }
 
-    fn get(&self, (y, x): (usize, usize)) -> &Tree {
-        &self.content[y * self.dim_x + x]
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
     }
 
-    fn score(&self, pos @ (y, x): (usize, usize)) -> usize {
-        if self.is_border(pos) {
-            return 0;
-        }
-
-        let tree = self.get(pos);
-        self.check_top(pos, tree).unwrap_or(y)
-            * self.check_bottom(pos, tree).unwrap_or(self.dim_y - y - 1)
-            * self.check_left(pos, tree).unwrap_or(x)
-            * self.check_right(pos, tree).unwrap_or(self.dim_x - x - 1)
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        solve_part2(input).map(Answer::from)
     }
 }
 
-pub fn input_gen(input: &str) -> Grid {
-    let rows = input.lines().count();
-    let cols = input
-        .chars()
-        .position(|c| c == '\n')
-        .unwrap_or_else(|| input.chars().count());
-
-    let trees = input
-        .lines()
-        .flat_map(|line| {
-            line.chars().map(move |c| Tree {
-                height: c.to_digit(10).unwrap(),
-            })
-        })
-        .collect();
-    Grid {
-        dim_x: cols,
-        dim_y: rows,
-        content: trees,
-    }
-}
-
-#[aoc(day8, part1)]
-pub fn solve_part1(input: &str) -> usize {
-    let grid @ Grid { dim_x, dim_y, .. } = input_gen(input);
-
-    (0..dim_y)
-        .map(|y| (0..dim_x).filter(|&x| grid.is_visible((y, x))).count())
-        .sum()
-}
-
-#[aoc(day8, part2)]
-pub fn solve_part2(input: &str) -> Option<usize> {
-    let grid @ Grid { dim_x, dim_y, .. } = input_gen(input);
-
-    (0..dim_y)
-        .filter_map(|y| (0..dim_x).map(|x| grid.score((y, x))).max())
-        .max()
-}
-
 #[cfg(test)]
 mod tests {
-    use super::{input_gen, solve_part1, solve_part2};
+    use super::{input_gen, score, solve_part1, solve_part2};
+    use crate::error::ParseError;
+    use crate::grid::Pos;
+    use crate::input::windows_style;
 
-    const INPUT: &str = "30373\n25512\n65332\n33549\n35390";
+    const INPUT: &str = include_str!("../fixtures/day08/example_1.txt");
 
     #[test]
     fn test_part1() -> anyhow::Result<()> {
-        let vis = solve_part1(INPUT);
+        let vis = solve_part1(&input_gen(INPUT)?);
         assert_eq!(vis, 21);
         Ok(())
     }
 
     #[test]
     fn test_score() -> anyhow::Result<()> {
-        let grid = input_gen(INPUT);
-        let score = grid.score((1, 2));
-        assert_eq!(score, 4);
+        let grid = input_gen(INPUT)?;
+        assert_eq!(score(&grid, Pos::new(2, 1)), 4);
 
         Ok(())
     }
 
     #[test]
     fn test_part2() -> anyhow::Result<()> {
-        let score = solve_part2(INPUT);
+        let score = solve_part2(&input_gen(INPUT)?);
         assert_eq!(score, Some(8));
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("30373\n2551\n65332").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(8, 2, 1, "2551", "expected a row of 5 cells")
+        );
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), 21);
+        assert_eq!(solve_part2(&parsed), Some(8));
+        Ok(())
+    }
 }
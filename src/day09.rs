@@ -1,7 +1,13 @@
-use aoc_runner_derive::aoc;
+use crate::error::ParseError;
+use crate::input::{self, normalize};
+use crate::solution::{Answer, Part, Solution};
+use crate::trace::{event, span};
+use aoc_runner_derive::{aoc, aoc_generator};
 use lending_iterator::prelude::*;
 use std::collections::HashSet;
+use std::io::BufRead;
 
+#[derive(Debug, Clone, Copy)]
 pub enum Direction {
     Left,
     Right,
@@ -9,29 +15,42 @@ pub enum Direction {
     Down,
 }
 
+#[derive(Debug)]
 pub struct Move {
     dir: Direction,
     count: usize,
 }
 
-pub fn input_gen(input: &str) -> impl Iterator<Item = Move> + '_ {
-    input.lines().filter_map(|line| {
-        let (dir, count) = line.split_once(' ')?;
-        let dir = match dir {
-            "L" => Direction::Left,
-            "R" => Direction::Right,
-            "U" => Direction::Up,
-            "D" => Direction::Down,
-            _ => return None,
-        };
+fn parse_line(idx: usize, line: &str) -> Result<Move, ParseError> {
+    let (dir, count) = line
+        .split_once(' ')
+        .ok_or_else(|| ParseError::line(9, idx, line, "expected `<direction> <count>`"))?;
+    let dir = match dir {
+        "L" => Direction::Left,
+        "R" => Direction::Right,
+        "U" => Direction::Up,
+        "D" => Direction::Down,
+        _ => return Err(ParseError::at(9, idx, line, dir, "expected L, R, U or D")),
+    };
 
-        Some(Move {
-            dir,
-            count: count.parse().ok()?,
-        })
+    Ok(Move {
+        dir,
+        count: count
+            .parse()
+            .map_err(|_| ParseError::at(9, idx, line, count, "expected a step count"))?,
     })
 }
 
+#[aoc_generator(day9)]
+pub fn input_gen(input: &str) -> Result<Vec<Move>, ParseError> {
+    let input = normalize(input);
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_line(idx, line))
+        .collect()
+}
+
 struct Rope<const N: usize> {
     knots: [(isize, isize); N],
     tail_positions: HashSet<(isize, isize)>,
@@ -69,9 +88,36 @@ impl<const N: usize> Rope<N> {
 
         // keep track of visited tail positions
         self.tail_positions.insert(self.knots[N - 1]);
+        event!(
+            head = ?self.knots[0],
+            tail = ?self.knots[N - 1],
+            knots = ?self.knots,
+            "step"
+        );
+    }
+
+    /// Draws the `width` x `height` cells with `corner` at the bottom left,
+    /// knots are drawn over visited tail positions.
+    fn draw(&self, corner: (isize, isize), width: isize, height: isize) -> String {
+        let mut drawing = String::new();
+        for y in (corner.1..corner.1 + height).rev() {
+            for x in corner.0..corner.0 + width {
+                drawing.push(match self.knots.iter().position(|&k| k == (x, y)) {
+                    Some(0) => 'H',
+                    Some(_) if N == 2 => 'T',
+                    Some(i) => char::from_digit(i as u32, 10).unwrap_or('+'),
+                    None if (x, y) == (0, 0) => 's',
+                    None if self.tail_positions.contains(&(x, y)) => '#',
+                    None => '.',
+                });
+            }
+            drawing.push('\n');
+        }
+        drawing
     }
 
     fn apply_move(&mut self, m: &Move) {
+        let _span = span!("move", knots = N, dir = ?m.dir, count = m.count);
         for _ in 0..m.count {
             self.step(&m.dir)
         }
@@ -79,11 +125,11 @@ impl<const N: usize> Rope<N> {
 }
 
 #[aoc(day9, part1)]
-pub fn solve_part1(input: &str) -> usize {
-    let moves = input_gen(input);
+pub fn solve_part1(moves: &[Move]) -> usize {
     moves
+        .iter()
         .fold(Rope::<2>::new(), |mut rope, m| {
-            rope.apply_move(&m);
+            rope.apply_move(m);
             rope
         })
         .tail_positions
@@ -91,42 +137,141 @@ pub fn solve_part1(input: &str) -> usize {
 }
 
 #[aoc(day9, part2)]
-pub fn solve_part2(input: &str) -> usize {
-    let moves = input_gen(input);
+pub fn solve_part2(moves: &[Move]) -> usize {
     moves
+        .iter()
         .fold(Rope::<10>::new(), |mut rope, m| {
-            rope.apply_move(&m);
+            rope.apply_move(m);
             rope
         })
         .tail_positions
         .len()
 }
 
+fn rope_frames<const N: usize>(moves: Vec<Move>) -> impl Iterator<Item = String> {
+    const WIDTH: isize = 41;
+    const HEIGHT: isize = 21;
+
+    let mut rope = Rope::<N>::new();
+    // the view only scrolls once the head would leave it
+    let mut corner = (-WIDTH / 2, -HEIGHT / 2);
+    let start = format!("start\n\n{}", rope.draw(corner, WIDTH, HEIGHT));
+    let steps = moves.into_iter().flat_map(|m| {
+        let name = match m.dir {
+            Direction::Left => 'L',
+            Direction::Right => 'R',
+            Direction::Up => 'U',
+            Direction::Down => 'D',
+        };
+        (1..=m.count).map(move |step| (name, step, m.count, m.dir))
+    });
+    std::iter::once(start).chain(steps.map(move |(name, step, count, dir)| {
+        rope.step(&dir);
+        let (x, y) = rope.knots[0];
+        corner.0 = corner.0.clamp(x - WIDTH + 1, x);
+        corner.1 = corner.1.clamp(y - HEIGHT + 1, y);
+        format!(
+            "{name} {count} ({step}/{count})\n\n{}",
+            rope.draw(corner, WIDTH, HEIGHT)
+        )
+    }))
+}
+
+/// One frame per step of the head, for the rope of 2 knots of part 1 or the
+/// one of 10 knots of part 2.
+pub fn frames(moves: Vec<Move>, part: Part) -> Box<dyn Iterator<Item = String>> {
+    match part {
+        Part::One => Box::new(rope_frames::<2>(moves)),
+        Part::Two => Box::new(rope_frames::<10>(moves)),
+    }
+}
+
+/// Solves both parts while reading `reader` line by line, both ropes are fed
+/// each move as soon as it is parsed.
+pub fn solve_stream<R: BufRead>(reader: R) -> anyhow::Result<(usize, usize)> {
+    let mut short = Rope::<2>::new();
+    let mut long = Rope::<10>::new();
+    for line in input::lines(reader) {
+        let (idx, line) = line?;
+        let m = parse_line(idx, &line)?;
+        short.apply_move(&m);
+        long.apply_move(&m);
+    }
+    Ok((short.tail_positions.len(), long.tail_positions.len()))
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input = Vec<Move>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{solve_part1, solve_part2};
+    use super::{input_gen, solve_part1, solve_part2, solve_stream};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
 
-    const INPUT: &str = "R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2";
-    const INPUT_2: &str = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20";
+    const INPUT: &str = include_str!("../fixtures/day09/example_1.txt");
+    const INPUT_2: &str = include_str!("../fixtures/day09/example_2.txt");
 
     #[test]
     fn test_part1() -> anyhow::Result<()> {
-        let count = solve_part1(INPUT);
+        let count = solve_part1(&input_gen(INPUT)?);
         assert_eq!(count, 13);
         Ok(())
     }
 
     #[test]
     fn test_part2() -> anyhow::Result<()> {
-        let count = solve_part2(INPUT);
+        let count = solve_part2(&input_gen(INPUT)?);
         assert_eq!(count, 1);
         Ok(())
     }
 
     #[test]
     fn test_part2_2() -> anyhow::Result<()> {
-        let count = solve_part2(INPUT_2);
+        let count = solve_part2(&input_gen(INPUT_2)?);
         assert_eq!(count, 36);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("R 4\nX 4").unwrap_err();
+        assert_eq!(err, ParseError::new(9, 2, 1, "X", "expected L, R, U or D"));
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        assert_eq!(solve_part1(&input_gen(&windows_style(INPUT))?), 13);
+        assert_eq!(solve_part2(&input_gen(&windows_style(INPUT_2))?), 36);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream() -> anyhow::Result<()> {
+        assert_eq!(solve_stream(INPUT.as_bytes())?, (13, 1));
+        assert_eq!(solve_stream(windows_style(INPUT_2).as_bytes())?, (88, 36));
+
+        let err = solve_stream("R 4\nX 4".as_bytes()).unwrap_err();
+        assert_eq!(
+            err.downcast::<ParseError>()?,
+            ParseError::new(9, 2, 1, "X", "expected L, R, U or D")
+        );
+        Ok(())
+    }
 }
@@ -1,5 +1,11 @@
-use aoc_runner_derive::aoc;
+use crate::error::ParseError;
+use crate::input::{self, normalize};
+use crate::solution::{Answer, Solution};
+use crate::trace::event;
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::io::BufRead;
 
+/// An instruction of the handheld's CPU.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Instruction {
     NoOp,
@@ -7,7 +13,8 @@ pub enum Instruction {
 }
 
 impl Instruction {
-    fn cycles(&self) -> usize {
+    /// Number of cycles the instruction takes to complete.
+    pub fn cycles(&self) -> usize {
         match self {
             Instruction::NoOp => 1,
             Instruction::AddX(_) => 2,
@@ -15,9 +22,11 @@ impl Instruction {
     }
 }
 
+/// The CPU with its single `X` register, running a program of instructions.
 pub struct Cpu<I> {
     program: I,
     x: i64,
+    cycle: usize,
     last_instruction: Option<(Instruction, usize)>,
 }
 
@@ -25,22 +34,34 @@ impl<I> Cpu<I>
 where
     I: Iterator<Item = Instruction>,
 {
-    fn new(program: I) -> Self {
+    /// A CPU about to run `program`, with `X` starting at 1.
+    pub fn new(program: I) -> Self {
         Self {
             program,
             x: 1,
+            cycle: 0,
             last_instruction: None,
         }
     }
 
-    fn single_step(&mut self) -> i64 {
+    /// Runs one cycle and returns the value of `X` during it. Once the program
+    /// has ended `X` keeps its last value.
+    pub fn single_step(&mut self) -> i64 {
         let x = self.x;
+        self.cycle += 1;
 
         // we either take the last unfinished instruction or we take the next on
         // of the program, if there is none, the program ended
         let Some((inst, left_cycles)) = self.last_instruction.take().or_else(|| {
-            self.program.by_ref().next().map(|inst| (inst, inst.cycles()-1))
-        }) else {return x};
+            self.program
+                .by_ref()
+                .next()
+                .map(|inst| (inst, inst.cycles() - 1))
+        }) else {
+            event!(cycle = self.cycle, x, "halted");
+            return x;
+        };
+        event!(cycle = self.cycle, x, instruction = ?inst, "cycle");
 
         // if there are still cycles left, we wait
         if left_cycles > 0 {
@@ -56,12 +77,23 @@ where
         x
     }
 
-    fn step(&mut self, cycles: usize) -> i64 {
+    /// Runs `cycles` cycles and returns the value of `X` during the last one.
+    pub fn step(&mut self, cycles: usize) -> i64 {
         (0..cycles).fold(0, |_, _| self.single_step())
     }
 }
 
-struct Crt<I> {
+/// Whether the 3 pixel wide sprite at `x` lights the pixel in `column`.
+fn pixel(column: i64, x: i64) -> char {
+    if (column - 1..=column + 1).contains(&x) {
+        '#'
+    } else {
+        '.'
+    }
+}
+
+/// The 40x6 screen, drawing one pixel per cycle of its CPU.
+pub struct Crt<I> {
     cpu: Cpu<I>,
 }
 
@@ -69,24 +101,18 @@ impl<I> Crt<I>
 where
     I: Iterator<Item = Instruction>,
 {
-    fn new(cpu: Cpu<I>) -> Self {
+    pub fn new(cpu: Cpu<I>) -> Self {
         Self { cpu }
     }
 
     fn draw_line(&mut self) -> impl Iterator<Item = char> + '_ {
         (0..40)
-            .map(|i| {
-                let x = self.cpu.single_step();
-                if (i - 1..=i + 1).contains(&x) {
-                    '#'
-                } else {
-                    '.'
-                }
-            })
+            .map(|i| pixel(i, self.cpu.single_step()))
             .chain(std::iter::once('\n'))
     }
 
-    fn draw(&mut self) -> String {
+    /// Draws a full screen, `#` for lit pixels, each row ending in a newline.
+    pub fn draw(&mut self) -> String {
         let mut result = String::new();
 
         for _ in 0..6 {
@@ -97,23 +123,31 @@ where
     }
 }
 
-fn input_gen(input: &str) -> impl Iterator<Item = Instruction> + '_ {
-    input.lines().filter_map(|line| {
-        if line.starts_with("noop") {
-            Some(Instruction::NoOp)
-        } else if line.starts_with("addx") {
-            let (_, val) = line.split_once(' ')?;
-            val.parse().map(Instruction::AddX).ok()
-        } else {
-            None
-        }
-    })
+fn parse_line(idx: usize, line: &str) -> Result<Instruction, ParseError> {
+    if line == "noop" {
+        Ok(Instruction::NoOp)
+    } else if let Some(val) = line.strip_prefix("addx ") {
+        val.parse()
+            .map(Instruction::AddX)
+            .map_err(|_| ParseError::at(10, idx, line, val, "expected an integer"))
+    } else {
+        Err(ParseError::line(10, idx, line, "unknown instruction"))
+    }
+}
+
+#[aoc_generator(day10)]
+pub fn input_gen(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let input = normalize(input);
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_line(idx, line))
+        .collect()
 }
 
 #[aoc(day10, part1)]
-pub fn solve_part1(input: &str) -> i64 {
-    let program = input_gen(input);
-    let mut cpu = Cpu::new(program);
+pub fn solve_part1(program: &[Instruction]) -> i64 {
+    let mut cpu = Cpu::new(program.iter().copied());
     (20..=220)
         .step_by(40)
         .enumerate()
@@ -125,32 +159,128 @@ pub fn solve_part1(input: &str) -> i64 {
 }
 
 #[aoc(day10, part2)]
-pub fn solve_part2(input: &str) -> String {
-    let program = input_gen(input);
-    let cpu = Cpu::new(program);
+pub fn solve_part2(program: &[Instruction]) -> String {
+    let cpu = Cpu::new(program.iter().copied());
     let mut crt = Crt::new(cpu);
     crt.draw()
 }
 
+/// Solves both parts in one run of the CPU, which reads its program from
+/// `reader` one instruction at a time.
+///
+/// Only the instructions needed for the 240 cycles of the screen are read.
+pub fn solve_stream<R: BufRead>(reader: R) -> anyhow::Result<(i64, String)> {
+    let mut error = None;
+    let mut lines = input::lines(reader);
+    let program = std::iter::from_fn(|| {
+        let parsed = lines
+            .next()?
+            .map_err(anyhow::Error::from)
+            .and_then(|(idx, line)| parse_line(idx, &line).map_err(anyhow::Error::from));
+        parsed.map_err(|err| error = Some(err)).ok()
+    });
+
+    let mut cpu = Cpu::new(program);
+    let mut strength = 0;
+    let mut screen = String::new();
+    for cycle in 1..=240 {
+        let x = cpu.single_step();
+        if cycle % 40 == 20 {
+            strength += cycle * x;
+        }
+        let column = (cycle - 1) % 40;
+        screen.push(pixel(column, x));
+        if column == 39 {
+            screen.push('\n');
+        }
+    }
+
+    match error {
+        Some(err) => Err(err),
+        None => Ok((strength, screen)),
+    }
+}
+
+/// One frame per cycle of the CRT drawing the screen, with the sprite
+/// position above the pixels drawn so far.
+pub fn frames(program: Vec<Instruction>) -> impl Iterator<Item = String> {
+    let mut cpu = Cpu::new(program.into_iter());
+    let mut screen = String::new();
+    (1..=240).map(move |cycle| {
+        let x = cpu.single_step();
+        let column = (cycle - 1) % 40;
+        screen.push(pixel(column, x));
+        if column == 39 {
+            screen.push('\n');
+        }
+        let sprite = (0..40).map(|c| pixel(c, x)).collect::<String>();
+        format!("cycle {cycle:3}, X = {x}\n{sprite}\n\n{screen}")
+    })
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(Answer::Text(solve_part2(input)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{solve_part1, solve_part2};
+    use super::{input_gen, solve_part1, solve_part2, solve_stream};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
 
-    const INPUT: &str = "addx 15\naddx -11\naddx 6\naddx -3\naddx 5\naddx -1\naddx -8\naddx 13\naddx 4\nnoop\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx 5\naddx -1\naddx -35\naddx 1\naddx 24\naddx -19\naddx 1\naddx 16\naddx -11\nnoop\nnoop\naddx 21\naddx -15\nnoop\nnoop\naddx -3\naddx 9\naddx 1\naddx -3\naddx 8\naddx 1\naddx 5\nnoop\nnoop\nnoop\nnoop\nnoop\naddx -36\nnoop\naddx 1\naddx 7\nnoop\nnoop\nnoop\naddx 2\naddx 6\nnoop\nnoop\nnoop\nnoop\nnoop\naddx 1\nnoop\nnoop\naddx 7\naddx 1\nnoop\naddx -13\naddx 13\naddx 7\nnoop\naddx 1\naddx -33\nnoop\nnoop\nnoop\naddx 2\nnoop\nnoop\nnoop\naddx 8\nnoop\naddx -1\naddx 2\naddx 1\nnoop\naddx 17\naddx -9\naddx 1\naddx 1\naddx -3\naddx 11\nnoop\nnoop\naddx 1\nnoop\naddx 1\nnoop\nnoop\naddx -13\naddx -19\naddx 1\naddx 3\naddx 26\naddx -30\naddx 12\naddx -1\naddx 3\naddx 1\nnoop\nnoop\nnoop\naddx -9\naddx 18\naddx 1\naddx 2\nnoop\nnoop\naddx 9\nnoop\nnoop\nnoop\naddx -1\naddx 2\naddx -37\naddx 1\naddx 3\nnoop\naddx 15\naddx -21\naddx 22\naddx -6\naddx 1\nnoop\naddx 2\naddx 1\nnoop\naddx -10\nnoop\nnoop\naddx 20\naddx 1\naddx 2\naddx 2\naddx -6\naddx -11\nnoop\nnoop\nnoop";
+    const INPUT: &str = include_str!("../fixtures/day10/example_1.txt");
 
     const SCREEN: &str = "##..##..##..##..##..##..##..##..##..##..\n###...###...###...###...###...###...###.\n####....####....####....####....####....\n#####.....#####.....#####.....#####.....\n######......######......######......####\n#######.......#######.......#######.....\n";
 
     #[test]
     fn test_part1() -> anyhow::Result<()> {
-        let sum = solve_part1(INPUT);
+        let sum = solve_part1(&input_gen(INPUT)?);
         assert_eq!(sum, 13140);
         Ok(())
     }
 
     #[test]
     fn test_part2() -> anyhow::Result<()> {
-        let screen = solve_part2(INPUT);
+        let screen = solve_part2(&input_gen(INPUT)?);
         assert_eq!(screen, SCREEN);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("noop\naddx 1O").unwrap_err();
+        assert_eq!(err, ParseError::new(10, 2, 6, "1O", "expected an integer"));
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), 13140);
+        assert_eq!(solve_part2(&parsed), SCREEN);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream() -> anyhow::Result<()> {
+        let expected = (13140, SCREEN.to_owned());
+        assert_eq!(solve_stream(INPUT.as_bytes())?, expected);
+        assert_eq!(solve_stream(windows_style(INPUT).as_bytes())?, expected);
+        assert!(solve_stream("noop\naddx x".as_bytes()).is_err());
+        Ok(())
+    }
 }
@@ -1,10 +1,14 @@
-use aoc_runner_derive::aoc;
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
+use crate::trace::{event, span};
+use aoc_runner_derive::{aoc, aoc_generator};
 
 #[derive(Debug, Default, Clone, Copy)]
 enum Operand {
     #[default]
     Lvl,
-    Factor(u32),
+    Factor(u64),
 }
 
 impl std::str::FromStr for Operand {
@@ -14,7 +18,7 @@ impl std::str::FromStr for Operand {
         if s == "old" {
             Ok(Self::Lvl)
         } else {
-            u32::from_str(s).map(Self::Factor)
+            u64::from_str(s).map(Self::Factor)
         }
     }
 }
@@ -26,20 +30,25 @@ enum Operation {
     Mul,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Monkey {
-    items: Vec<u32>,
+    items: Vec<u64>,
     op: Operation,
     lhs: Operand,
     rhs: Operand,
-    test_divisor: u32,
+    test_divisor: u64,
     test_success: usize,
     test_failure: usize,
     inspected: usize,
 }
 
 impl Monkey {
-    fn turn(&mut self, div: u32) -> impl Iterator<Item = (usize, u32)> + '_ {
+    /// Inspects and throws every item, `None` for an item whose worry level
+    /// `relief` doesn't bring back into a `u64`.
+    fn turn<'a, F>(&'a mut self, relief: F) -> impl Iterator<Item = Option<(usize, u64)>> + 'a
+    where
+        F: Fn(u128) -> Option<u64> + 'a,
+    {
         std::mem::take(&mut self.items)
             .into_iter()
             .map(|item| {
@@ -50,71 +59,201 @@ impl Monkey {
                     (Operand::Factor(f), Operand::Lvl) => (f, item),
                     (Operand::Factor(f1), Operand::Factor(f2)) => (f1, f2),
                 };
+                // neither can overflow with both operands in a `u64`
+                let (lhs, rhs) = (u128::from(lhs), u128::from(rhs));
                 match self.op {
                     Operation::Add => lhs + rhs,
                     Operation::Mul => lhs * rhs,
                 }
             })
-            .map(move |item| item / div)
+            .map(relief)
             .map(|item| {
-                if item % self.test_divisor == 0 {
-                    (self.test_success, item)
+                let item = item?;
+                let to = if item % self.test_divisor == 0 {
+                    self.test_success
                 } else {
-                    (self.test_failure, item)
-                }
+                    self.test_failure
+                };
+                event!(to, worry = item, "throw");
+                Some((to, item))
             })
     }
 }
 
-fn input_gen(input: &str) -> impl Iterator<Item = Monkey> + '_ {
-    input.split("\n\n").map(|chunk| {
-        let mut monkey = Monkey::default();
-        for line in chunk.lines() {
-            if let Some(items) = line.trim().strip_prefix("Starting items: ") {
-                monkey.items = items
-                    .split(", ")
-                    .map(|item| item.parse().unwrap())
-                    .collect();
-            }
+/// A monkey's notes, and where its divisor is.
+fn parse_monkey<'a, I>(
+    lines: &mut I,
+    header: (usize, &'a str),
+) -> Result<(Monkey, (usize, &'a str, &'a str)), ParseError>
+where
+    I: Iterator<Item = (usize, &'a str)>,
+{
+    let mut last = header;
+    let mut field = |prefix: &str| {
+        let (idx, line) = lines.next().unwrap_or((last.0 + 1, ""));
+        last = (idx, line);
+        line.trim()
+            .strip_prefix(prefix)
+            .map(|value| (idx, line, value))
+            .ok_or_else(|| ParseError::line(11, idx, line, format!("expected `{prefix}...`")))
+    };
+    fn number<T: std::str::FromStr>(
+        (idx, line, value): (usize, &str, &str),
+    ) -> Result<T, ParseError> {
+        value
+            .parse()
+            .map_err(|_| ParseError::at(11, idx, line, value, "expected a number"))
+    }
 
-            if let Some(op) = line.trim().strip_prefix("Operation: new = ") {
-                let mut ops = op.split(' ');
-                monkey.lhs = ops.next().unwrap().parse().unwrap();
+    let mut monkey = Monkey::default();
 
-                monkey.op = match ops.next() {
-                    Some("*") => Operation::Mul,
-                    Some("+") => Operation::Add,
-                    _ => panic!("Unknown operation"),
-                };
+    // a monkey may start without items, in which case normalizing the input
+    // has already stripped the space after the colon
+    let (idx, line, items) = field("Starting items:")?;
+    monkey.items = Some(items.trim_start())
+        .filter(|items| !items.is_empty())
+        .into_iter()
+        .flat_map(|items| items.split(", "))
+        .map(|item| number((idx, line, item)))
+        .collect::<Result<_, _>>()?;
 
-                monkey.rhs = ops.next().unwrap().parse().unwrap();
-            }
+    let (idx, line, op) = field("Operation: new = ")?;
+    let mut ops = op.split(' ');
+    let (Some(lhs), Some(operator), Some(rhs), None) =
+        (ops.next(), ops.next(), ops.next(), ops.next())
+    else {
+        return Err(ParseError::at(
+            11,
+            idx,
+            line,
+            op,
+            "expected `<lhs> <op> <rhs>`",
+        ));
+    };
+    let operand = |operand: &str| {
+        operand
+            .parse()
+            .map_err(|_| ParseError::at(11, idx, line, operand, "expected `old` or a number"))
+    };
+    monkey.lhs = operand(lhs)?;
+    monkey.op = match operator {
+        "*" => Operation::Mul,
+        "+" => Operation::Add,
+        _ => return Err(ParseError::at(11, idx, line, operator, "unknown operation")),
+    };
+    monkey.rhs = operand(rhs)?;
 
-            if let Some(divisor) = line.trim().strip_prefix("Test: divisible by ") {
-                monkey.test_divisor = divisor.parse().unwrap();
-            }
+    let test = field("Test: divisible by ")?;
+    monkey.test_divisor = number(test)?;
+    if monkey.test_divisor == 0 {
+        return Err(ParseError::at(
+            11,
+            test.0,
+            test.1,
+            test.2,
+            "divisor must not be zero",
+        ));
+    }
+    monkey.test_success = number(field("If true: throw to monkey ")?)?;
+    monkey.test_failure = number(field("If false: throw to monkey ")?)?;
 
-            if let Some(test_success) = line.trim().strip_prefix("If true: throw to monkey ") {
-                monkey.test_success = test_success.parse().unwrap();
-            }
+    Ok((monkey, test))
+}
 
-            if let Some(test_failure) = line.trim().strip_prefix("If false: throw to monkey ") {
-                monkey.test_failure = test_failure.parse().unwrap();
-            }
+#[aoc_generator(day11)]
+pub fn input_gen(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let input = normalize(input);
+    let mut lines = input.lines().enumerate();
+    let mut monkeys = vec![];
+    let mut headers = vec![];
+    let mut modulus = 1;
+
+    while let Some((idx, line)) = lines.next() {
+        if line.trim().is_empty() {
+            continue;
         }
-        monkey
-    })
+        if line.strip_prefix("Monkey ") != Some(&format!("{}:", monkeys.len())) {
+            return Err(ParseError::line(
+                11,
+                idx,
+                line,
+                format!("expected `Monkey {}:`", monkeys.len()),
+            ));
+        }
+        let (monkey, (test_idx, test_line, divisor)) = parse_monkey(&mut lines, (idx, line))?;
+        modulus = lcm(modulus, monkey.test_divisor).ok_or_else(|| {
+            ParseError::at(
+                11,
+                test_idx,
+                test_line,
+                divisor,
+                "divisors have no common multiple within 64 bits",
+            )
+        })?;
+        monkeys.push(monkey);
+        headers.push((idx, line));
+    }
+
+    for (i, (monkey, &(idx, line))) in monkeys.iter().zip(&headers).enumerate() {
+        let targets = [monkey.test_success, monkey.test_failure];
+        if targets.iter().any(|&to| to >= monkeys.len()) {
+            return Err(ParseError::line(
+                11,
+                idx,
+                line,
+                "throws to an unknown monkey",
+            ));
+        }
+        if targets.contains(&i) {
+            return Err(ParseError::line(11, idx, line, "throws to itself"));
+        }
+    }
+
+    Ok(monkeys)
 }
 
-#[aoc(day11, part1)]
-pub fn solve_part1(input: &str) -> usize {
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Least common multiple of `a` and `b`, `None` if it doesn't fit.
+fn lcm(a: u64, b: u64) -> Option<u64> {
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Least common multiple of the divisors of all monkeys.
+///
+/// The tests only ask whether a worry level is divisible, which doesn't
+/// change modulo a multiple of every divisor. The parser makes sure the least
+/// one fits in a `u64`, even when the product of the divisors doesn't, so
+/// levels kept below it fit too.
+fn worry_modulus(monkeys: &[Monkey]) -> u64 {
+    monkeys
+        .iter()
+        .try_fold(1, |modulus, m| lcm(modulus, m.test_divisor))
+        .expect("the parser checks the divisors")
+}
+
+// the round number is only read by the span
+#[cfg_attr(not(feature = "trace"), allow(unused_variables))]
+fn monkey_business(
+    mut monkeys: Vec<Monkey>,
+    rounds: usize,
+    relief: impl Fn(u128) -> Option<u64> + Copy,
+) -> Option<usize> {
     let mut dummy = Monkey::default();
-    let mut monkeys = input_gen(input).collect::<Vec<_>>();
 
-    for _ in 0..20 {
+    for round in 1..=rounds {
+        let _round = span!("round", round);
         for i in 0..monkeys.len() {
+            let _turn = span!("turn", monkey = i);
             std::mem::swap(&mut monkeys[i], &mut dummy);
-            for (idx, item) in dummy.turn(3) {
+            for thrown in dummy.turn(relief) {
+                let (idx, item) = thrown?;
                 monkeys[idx].items.push(item)
             }
             std::mem::swap(&mut monkeys[i], &mut dummy);
@@ -125,74 +264,165 @@ pub fn solve_part1(input: &str) -> usize {
         .into_iter()
         .map(|m| m.inspected)
         .fold((0, 0), |(a, b), count| (a.max(b), a.min(b).max(count)));
-    a * b
+    Some(a * b)
+}
+
+#[aoc(day11, part1)]
+pub fn solve_part1(monkeys: &[Monkey]) -> Option<usize> {
+    // squaring worry levels for 20 rounds may outgrow any integer
+    monkey_business(monkeys.to_vec(), 20, |lvl| u64::try_from(lvl / 3).ok())
 }
 
 #[aoc(day11, part2)]
-pub fn solve_part2(input: &str) -> usize {
-    let mut dummy = Monkey::default();
-    let mut monkeys = input_gen(input).collect::<Vec<_>>();
+pub fn solve_part2(monkeys: &[Monkey]) -> usize {
+    // without relief worry levels grow without bound, see `worry_modulus`
+    let modulus = u128::from(worry_modulus(monkeys));
+    monkey_business(monkeys.to_vec(), 10_000, move |lvl| {
+        u64::try_from(lvl % modulus).ok()
+    })
+    .expect("worry levels stay below the modulus")
+}
 
-    for _ in 0..10_000 {
-        for i in 0..monkeys.len() {
-            std::mem::swap(&mut monkeys[i], &mut dummy);
-            for (idx, item) in dummy.turn(1) {
-                monkeys[idx].items.push(item)
-            }
-            std::mem::swap(&mut monkeys[i], &mut dummy);
-        }
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
     }
 
-    let (a, b) = monkeys
-        .into_iter()
-        .map(|m| m.inspected)
-        .fold((0, 0), |(a, b), count| (a.max(b), a.min(b).max(count)));
-    a * b
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(input).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{solve_part1, solve_part2};
+    use super::{input_gen, solve_part1, solve_part2, worry_modulus};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
+
+    const INPUT: &str = include_str!("../fixtures/day11/example_1.txt");
 
-    const INPUT: &str = "Monkey 0:
-Starting items: 79, 98
-Operation: new = old * 19
-Test: divisible by 23
+    const OVERFLOW: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * old
+  Test: divisible by 65536
     If true: throw to monkey 2
     If false: throw to monkey 3
 
 Monkey 1:
-Starting items: 54, 65, 75, 74
-Operation: new = old + 6
-Test: divisible by 19
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 3
+  Test: divisible by 131072
     If true: throw to monkey 2
     If false: throw to monkey 0
 
 Monkey 2:
-Starting items: 79, 60, 97
-Operation: new = old * old
-Test: divisible by 13
+  Starting items: 79, 60, 97
+  Operation: new = old * 19
+  Test: divisible by 65536
     If true: throw to monkey 1
-    If false: throw to monkey 3
+    If false: throw to monkey 4
 
 Monkey 3:
-Starting items: 74
-Operation: new = old + 3
-Test: divisible by 17
+  Starting items: 74
+  Operation: new = old + old
+  Test: divisible by 262144
     If true: throw to monkey 0
-    If false: throw to monkey 1";
+    If false: throw to monkey 1
+
+Monkey 4:
+  Starting items: 2, 4
+  Operation: new = old * old
+  Test: divisible by 65536
+    If true: throw to monkey 3
+    If false: throw to monkey 0
+";
 
     #[test]
     fn test_part1() -> anyhow::Result<()> {
-        let lvl = solve_part1(INPUT);
-        assert_eq!(lvl, 10605);
+        let lvl = solve_part1(&input_gen(INPUT)?);
+        assert_eq!(lvl, Some(10605));
         Ok(())
     }
 
     #[test]
     fn test_part2() -> anyhow::Result<()> {
-        let lvl = solve_part2(INPUT);
+        let lvl = solve_part2(&input_gen(INPUT)?);
         assert_eq!(lvl, 2713310158);
         Ok(())
     }
+
+    #[test]
+    fn test_worry_modulus() -> anyhow::Result<()> {
+        assert_eq!(worry_modulus(&input_gen(INPUT)?), 23 * 19 * 13 * 17);
+        // the divisors multiply to 2^82, worry levels stay below 2^18 and
+        // squaring them fits
+        let monkeys = input_gen(OVERFLOW)?;
+        assert_eq!(worry_modulus(&monkeys), 1 << 18);
+        assert_eq!(solve_part2(&monkeys), 3599940000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = INPUT.replace("old + 6", "old / 6");
+        let err = input_gen(&input).unwrap_err();
+        assert_eq!(err, ParseError::new(11, 10, 22, "/", "unknown operation"));
+    }
+
+    #[test]
+    fn test_huge_numbers() -> anyhow::Result<()> {
+        let input = INPUT
+            .replace("by 23", "by 999983")
+            .replace("by 19", "by 999979")
+            .replace("by 13", "by 999961");
+        let monkeys = input_gen(&input)?;
+        assert_eq!(
+            solve_part2(&monkeys),
+            crate::reference::day11(&input, 10_000, false)
+        );
+
+        let err = input_gen(&input.replace("by 17", "by 999959")).unwrap_err();
+        let reason = "divisors have no common multiple within 64 bits";
+        assert_eq!(err, ParseError::new(11, 25, 20, "999959", reason));
+
+        // monkey 2 squares its items
+        let input = INPUT.replace("79, 60, 97", "18446744073709551615");
+        assert_eq!(solve_part1(&input_gen(&input)?), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_throw_to_itself() {
+        let input = INPUT.replace("If true: throw to monkey 2", "If true: throw to monkey 0");
+        let err = input_gen(&input).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(11, 1, 1, "Monkey 0:", "throws to itself")
+        );
+    }
+
+    #[test]
+    fn test_no_items() -> anyhow::Result<()> {
+        let monkeys = input_gen(&INPUT.replace("Starting items: 74", "Starting items: "))?;
+        assert!(monkeys[3].items.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), Some(10605));
+        assert_eq!(solve_part2(&parsed), 2713310158);
+        Ok(())
+    }
 }
@@ -1,45 +1,34 @@
-use aoc_runner_derive::aoc;
+use crate::error::ParseError;
+use crate::grid::{Grid, Pos};
+use crate::input::normalize;
+use crate::solution::{Answer, Part, Solution};
+use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::{HashSet, VecDeque};
 
-pub struct Grid {
-    dim_x: usize,
-    dim_y: usize,
-    end: usize,
-    content: Vec<u32>,
+#[derive(Debug)]
+pub struct Heightmap {
+    elevations: Grid<u32>,
+    end: Pos,
 }
 
-impl Grid {
-    fn get(&self, (x, y): (usize, usize)) -> u32 {
-        self.content[y * self.dim_x + x]
-    }
-
-    fn lowest(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
-        self.content
+impl Heightmap {
+    fn lowest(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.elevations
             .iter()
-            .enumerate()
             .filter(|&(_, e)| *e == 0)
-            .map(|(i, _)| (i % self.dim_x, i / self.dim_x))
+            .map(|(pos, _)| pos)
     }
 
-    fn find_path(&self, start: (usize, usize)) -> Option<usize> {
+    fn find_path(&self, start: Pos) -> Option<usize> {
         let mut visited = HashSet::new();
         let mut queue: VecDeque<(usize, _)> = VecDeque::new();
         queue.push_back((0, start));
         visited.insert(start);
 
-        while let Some((count, pos @ (x, y))) = queue.pop_front() {
-            for n_pos @ (nx, ny) in [
-                (x + 1, y),
-                (x.saturating_sub(1), y),
-                (x, y + 1),
-                (x, y.saturating_sub(1)),
-            ] {
-                if nx < self.dim_x
-                    && ny < self.dim_y
-                    && self.get(n_pos) <= self.get(pos) + 1
-                    && visited.insert(n_pos)
-                {
-                    if ny * self.dim_x + nx == self.end {
+        while let Some((count, pos)) = queue.pop_front() {
+            for n_pos in self.elevations.neighbours4(pos) {
+                if self.elevations[n_pos] <= self.elevations[pos] + 1 && visited.insert(n_pos) {
+                    if n_pos == self.end {
                         return Some(count + 1);
                     }
                     queue.push_back((count + 1, n_pos))
@@ -50,79 +39,166 @@ impl Grid {
     }
 }
 
-pub fn input_gen(input: &str) -> ((usize, usize), Grid) {
-    let rows = input.lines().count();
-    let cols = input
-        .chars()
-        .position(|c| c == '\n')
-        .unwrap_or_else(|| input.chars().count());
-
-    let elevations = input
-        .lines()
-        .flat_map(|line| {
-            line.chars().map(move |c| match c {
-                'a'..='z' => u32::from(c) - u32::from('a'),
-                'S' => 0,
-                'E' => 25,
-                _ => panic!(),
-            })
-        })
-        .collect();
-
-    let start = input
-        .chars()
-        .filter(|&c| c != '\n')
-        .position(|c| c == 'S')
-        .unwrap();
-    let end = input
-        .chars()
-        .filter(|&c| c != '\n')
-        .position(|c| c == 'E')
-        .unwrap();
-
-    let start_x = start % cols;
-    let start_y = start / cols;
-
-    (
-        (start_x, start_y),
-        Grid {
-            dim_x: cols,
-            dim_y: rows,
-            end,
-            content: elevations,
-        },
-    )
+#[aoc_generator(day12)]
+pub fn input_gen(input: &str) -> Result<(Pos, Heightmap), ParseError> {
+    let input = normalize(input);
+    let mut start = None;
+    let mut end = None;
+    let elevations = Grid::parse(12, &input, |pos, c| {
+        let marker = match c {
+            'S' => Some(&mut start),
+            'E' => Some(&mut end),
+            _ => None,
+        };
+        if let Some(marker) = marker {
+            if marker.is_some() {
+                return Err("duplicate marker");
+            }
+            *marker = Some(pos);
+        }
+
+        match c {
+            'a'..='z' => Ok(u32::from(c) - u32::from('a')),
+            'S' => Ok(0),
+            'E' => Ok(25),
+            _ => Err("expected a-z, S or E"),
+        }
+    })?;
+
+    let missing = |marker| {
+        let (idx, line) = input.lines().enumerate().last().unwrap_or((0, ""));
+        ParseError::line(12, idx, line, format!("missing marker {marker}"))
+    };
+    let start = start.ok_or_else(|| missing('S'))?;
+    let end = end.ok_or_else(|| missing('E'))?;
+
+    Ok((start, Heightmap { elevations, end }))
 }
 
 #[aoc(day12, part1)]
-pub fn solve_part1(input: &str) -> Option<usize> {
-    let (start, grid) = input_gen(input);
-    grid.find_path(start)
+pub fn solve_part1((start, map): &(Pos, Heightmap)) -> Option<usize> {
+    map.find_path(*start)
 }
 
 #[aoc(day12, part2)]
-pub fn solve_part2(input: &str) -> Option<usize> {
-    let (_, grid) = input_gen(input);
-    grid.lowest().filter_map(|p| grid.find_path(p)).min()
+pub fn solve_part2((_, map): &(Pos, Heightmap)) -> Option<usize> {
+    map.lowest().filter_map(|p| map.find_path(p)).min()
+}
+
+/// One frame per step of a breadth-first search, from the start for part 1
+/// or from every lowest square at once for part 2, until it reaches the end.
+///
+/// Squares not reached yet show their elevation, reached ones a `.` and the
+/// frontier a `@`.
+pub fn frames((start, map): (Pos, Heightmap), part: Part) -> impl Iterator<Item = String> {
+    let elevations = &map.elevations;
+    let mut visited = Grid::new(
+        elevations.width(),
+        elevations.height(),
+        vec![false; elevations.width() * elevations.height()],
+    );
+    let mut frontier = match part {
+        Part::One => vec![start],
+        Part::Two => map.lowest().collect(),
+    };
+    for &pos in &frontier {
+        visited[pos] = true;
+    }
+
+    let mut step = 0;
+    std::iter::from_fn(move || {
+        if frontier.is_empty() {
+            return None;
+        }
+        let elevations = &map.elevations;
+        let cells = elevations
+            .iter()
+            .map(|(pos, &e)| match visited[pos] {
+                true => '.',
+                false if pos == map.end => 'E',
+                false => char::from(b'a' + e as u8),
+            })
+            .collect();
+        let mut drawing = Grid::new(elevations.width(), elevations.height(), cells);
+        for &pos in &frontier {
+            drawing[pos] = '@';
+        }
+        let frame = format!("step {step}, {} squares\n\n{drawing}", frontier.len());
+
+        if visited[map.end] {
+            frontier.clear();
+        } else {
+            let mut next = vec![];
+            for pos in frontier.drain(..) {
+                for n_pos in elevations.neighbours4(pos) {
+                    if elevations[n_pos] <= elevations[pos] + 1 && !visited[n_pos] {
+                        visited[n_pos] = true;
+                        next.push(n_pos);
+                    }
+                }
+            }
+            frontier = next;
+        }
+        step += 1;
+        Some(frame)
+    })
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = (Pos, Heightmap);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        solve_part2(input).map(Answer::from)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{solve_part1, solve_part2};
+    use super::{input_gen, solve_part1, solve_part2};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
 
-    const INPUT: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi";
+    const INPUT: &str = include_str!("../fixtures/day12/example_1.txt");
 
     #[test]
     fn test_part1() -> anyhow::Result<()> {
-        let count = solve_part1(INPUT);
+        let count = solve_part1(&input_gen(INPUT)?);
         assert_eq!(count, Some(31));
         Ok(())
     }
 
     #[test]
     fn test_part2() -> anyhow::Result<()> {
-        let count = solve_part2(INPUT);
+        let count = solve_part2(&input_gen(INPUT)?);
         assert_eq!(count, Some(29));
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("Sabqponm\nabcryxxl\naccszxxk").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(12, 3, 1, "accszxxk", "missing marker E")
+        );
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), Some(31));
+        assert_eq!(solve_part2(&parsed), Some(29));
+        Ok(())
+    }
 }
@@ -1,49 +1,132 @@
-use aoc_runner_derive::aoc;
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
 
+/// Deepest lists may be nested, parsing, comparing and dropping packets
+/// recurses into them.
+pub const MAX_DEPTH: usize = 1000;
+
+/// A distress signal packet, or one of its elements.
 #[derive(Debug, Clone, PartialEq, Eq)]
-enum PacketPart {
+pub enum PacketPart {
     Integer(u32),
     List(Vec<PacketPart>),
 }
 
-fn parse_packet_inner<I>(chars: &mut I, parts: &mut Vec<PacketPart>)
-where
-    I: Iterator<Item = char>,
-{
-    let mut num = String::new();
-
-    loop {
-        let Some(c) = chars.next() else {return;};
-        match c {
-            '[' => {
-                let mut p = vec![];
-                parse_packet_inner(chars, &mut p);
-                parts.push(PacketPart::List(p));
+impl PacketPart {
+    /// Compares two packets by the rules of the distress signal, `Less` means
+    /// they are in the right order.
+    ///
+    /// This is not `Ord`: `[1]` and `1` compare equal without being equal.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        match compare_packets(self, other) {
+            Some(Order::Correct) => Ordering::Less,
+            Some(Order::Reversed) => Ordering::Greater,
+            None => Ordering::Equal,
+        }
+    }
+}
+
+impl FromStr for PacketPart {
+    type Err = ParseError;
+
+    /// Parses a single packet such as `[1,[2,3]]`.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        parse_packet(0, line)
+    }
+}
+
+fn parse_packet_inner(
+    idx: usize,
+    line: &str,
+    chars: &mut Peekable<CharIndices>,
+    depth: usize,
+) -> Result<PacketPart, ParseError> {
+    let error = |pos: usize, reason: &str| {
+        let rest = &line[pos..];
+        let token = rest.chars().next().map_or(rest, |c| &rest[..c.len_utf8()]);
+        ParseError::at(13, idx, line, token, reason)
+    };
+
+    match chars.next() {
+        Some((pos, '[')) if depth == MAX_DEPTH => {
+            Err(error(pos, &format!("lists nested deeper than {MAX_DEPTH}")))
+        }
+        Some((_, '[')) => {
+            let mut parts = vec![];
+            if let Some((_, ']')) = chars.peek() {
+                chars.next();
+                return Ok(PacketPart::List(parts));
             }
-            ']' => {
-                if !num.is_empty() {
-                    parts.push(PacketPart::Integer(num.parse().unwrap()));
+            loop {
+                parts.push(parse_packet_inner(idx, line, chars, depth + 1)?);
+                match chars.next() {
+                    Some((_, ',')) => continue,
+                    Some((_, ']')) => return Ok(PacketPart::List(parts)),
+                    Some((pos, _)) => return Err(error(pos, "expected `,` or `]`")),
+                    None => return Err(error(line.len(), "unclosed list")),
                 }
-                num.clear();
-                return;
             }
-            ',' => {
-                if !num.is_empty() {
-                    parts.push(PacketPart::Integer(num.parse().unwrap()));
-                }
-                num.clear();
+        }
+        Some((start, '0'..='9')) => {
+            let mut end = start + 1;
+            while let Some(&(pos, '0'..='9')) = chars.peek() {
+                end = pos + 1;
+                chars.next();
             }
-
-            '0'..='9' => num.push(c),
-            _ => unreachable!(),
+            line[start..end]
+                .parse()
+                .map(PacketPart::Integer)
+                .map_err(|_| ParseError::at(13, idx, line, &line[start..end], "integer too large"))
         }
+        Some((pos, _)) => Err(error(pos, "expected `[` or an integer")),
+        None => Err(error(line.len(), "unexpected end of packet")),
+    }
+}
+
+fn parse_packet(idx: usize, line: &str) -> Result<PacketPart, ParseError> {
+    if !line.starts_with('[') {
+        return Err(ParseError::line(13, idx, line, "expected a list"));
+    }
+    let mut chars = line.char_indices().peekable();
+    let packet = parse_packet_inner(idx, line, &mut chars, 0)?;
+    match chars.next() {
+        Some((pos, _)) => Err(ParseError::at(
+            13,
+            idx,
+            line,
+            &line[pos..],
+            "trailing characters",
+        )),
+        None => Ok(packet),
     }
 }
 
-fn parse_packet(input: &str) -> Vec<PacketPart> {
-    let mut v = vec![];
-    parse_packet_inner(&mut input.chars(), &mut v);
-    v
+#[aoc_generator(day13)]
+pub fn input_gen(input: &str) -> Result<Vec<PacketPart>, ParseError> {
+    let input = normalize(input);
+    let packets = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| parse_packet(idx, line))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if packets.len() % 2 != 0 {
+        let (idx, line) = input.lines().enumerate().last().unwrap_or((0, ""));
+        return Err(ParseError::line(
+            13,
+            idx,
+            line,
+            "packet is missing its pair",
+        ));
+    }
+
+    Ok(packets)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -86,15 +169,10 @@ fn compare_packets(l: &PacketPart, r: &PacketPart) -> Option<Order> {
 }
 
 #[aoc(day13, part1)]
-pub fn solve_part1(input: &str) -> usize {
-    input
-        .split("\n\n")
-        .filter_map(|pair| {
-            let (l, r) = pair.split_once('\n')?;
-            let l = PacketPart::List(parse_packet(l));
-            let r = PacketPart::List(parse_packet(r));
-            Some(compare_packets(&l, &r))
-        })
+pub fn solve_part1(packets: &[PacketPart]) -> usize {
+    packets
+        .chunks_exact(2)
+        .map(|pair| compare_packets(&pair[0], &pair[1]))
         .enumerate()
         .filter(|(_, c)| matches!(c, Some(Order::Correct)))
         .map(|(i, _)| i + 1)
@@ -102,24 +180,16 @@ pub fn solve_part1(input: &str) -> usize {
 }
 
 #[aoc(day13, part2)]
-pub fn solve_part2(input: &str) -> usize {
+pub fn solve_part2(packets: &[PacketPart]) -> usize {
     let div_packet_1 = PacketPart::List(vec![PacketPart::List(vec![PacketPart::Integer(2)])]);
     let div_packet_2 = PacketPart::List(vec![PacketPart::List(vec![PacketPart::Integer(6)])]);
 
-    let mut packets: Vec<_> = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|packet| PacketPart::List(parse_packet(packet)))
-        .collect();
+    let mut packets = packets.to_vec();
 
     packets.push(div_packet_1.clone());
     packets.push(div_packet_2.clone());
 
-    packets.sort_by(|l, r| match compare_packets(r, l) {
-        Some(Order::Correct) => std::cmp::Ordering::Greater,
-        Some(Order::Reversed) => std::cmp::Ordering::Less,
-        None => std::cmp::Ordering::Equal,
-    });
+    packets.sort_by(PacketPart::compare);
     let (ia, ib) = packets
         .into_iter()
         .enumerate()
@@ -135,23 +205,90 @@ pub fn solve_part2(input: &str) -> usize {
     ia * ib
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Vec<PacketPart>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{solve_part1, solve_part2};
+    use super::{input_gen, solve_part1, solve_part2, PacketPart, MAX_DEPTH};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
+    use std::cmp::Ordering;
 
-    const INPUT: &str = "[1,1,3,1,1]\n[1,1,5,1,1]\n\n[[1],[2,3,4]]\n[[1],4]\n\n[9]\n[[8,7,6]]\n\n[[4,4],4,4]\n[[4,4],4,4,4]\n\n[7,7,7,7]\n[7,7,7]\n\n[]\n[3]\n\n[[[]]]\n[[]]\n\n[1,[2,[3,[4,[5,6,7]]]],8,9]\n[1,[2,[3,[4,[5,6,0]]]],8,9]";
+    const INPUT: &str = include_str!("../fixtures/day13/example_1.txt");
 
     #[test]
     fn test_part1() -> anyhow::Result<()> {
-        let sum = solve_part1(INPUT);
+        let sum = solve_part1(&input_gen(INPUT)?);
         assert_eq!(sum, 13);
         Ok(())
     }
 
     #[test]
     fn test_part2() -> anyhow::Result<()> {
-        let key = solve_part2(INPUT);
+        let key = solve_part2(&input_gen(INPUT)?);
         assert_eq!(key, 140);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("[1,1,3,1,1]\n[1,[1;5],1,1]").unwrap_err();
+        assert_eq!(err, ParseError::new(13, 2, 6, ";", "expected `,` or `]`"));
+    }
+
+    #[test]
+    fn test_depth() -> anyhow::Result<()> {
+        let nested = |depth| format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+        let deepest = nested(MAX_DEPTH).parse::<PacketPart>()?;
+        assert_eq!(deepest.compare(&deepest.clone()), Ordering::Equal);
+        let err = input_gen(&format!("[]\n{}", nested(MAX_DEPTH + 1))).unwrap_err();
+        let reason = format!("lists nested deeper than {MAX_DEPTH}");
+        assert_eq!(err, ParseError::new(13, 2, MAX_DEPTH + 1, "[", reason));
+        assert!(input_gen(&"[".repeat(200_000)).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare() -> anyhow::Result<()> {
+        let packet = |s: &str| s.parse::<PacketPart>();
+        assert_eq!(
+            packet("[[1],[2,3,4]]")?.compare(&packet("[[1],4]")?),
+            Ordering::Less
+        );
+        assert_eq!(
+            packet("[9]")?.compare(&packet("[[8,7,6]]")?),
+            Ordering::Greater
+        );
+        assert_eq!(packet("[1]")?.compare(&packet("[[1]]")?), Ordering::Equal);
+        assert_eq!(
+            packet("[1,]").unwrap_err(),
+            ParseError::new(13, 1, 4, "]", "expected `[` or an integer")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), 13);
+        assert_eq!(solve_part2(&parsed), 140);
+        Ok(())
+    }
 }
@@ -0,0 +1,313 @@
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Part, Solution};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A position in the cave, `y` grows downwards.
+pub type Point = (i32, i32);
+
+/// Where the sand pours in unless configured otherwise.
+pub const SOURCE: Point = (500, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Rock,
+    Sand,
+}
+
+/// What is below the lowest rock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bottom {
+    /// Nothing, sand falling past the lowest rock is lost.
+    Abyss,
+    /// An endless floor two below the lowest rock.
+    Floor,
+}
+
+/// A slice of the cave, only rock and resting sand are stored.
+#[derive(Debug, Clone)]
+pub struct Cave {
+    tiles: HashMap<Point, Tile>,
+    /// `y` of the lowest rock.
+    depth: i32,
+    source: Point,
+    bottom: Bottom,
+    resting: usize,
+    /// The fall of the last grain, minus where it came to rest. The next
+    /// grain falls the same way, so it can start at the end of this path.
+    path: Vec<Point>,
+}
+
+impl Cave {
+    /// A cave with rock along the polylines of `paths`, sand pours in at
+    /// [`SOURCE`] and is lost in the abyss.
+    pub fn new(paths: &[Vec<Point>]) -> Self {
+        let mut tiles = HashMap::new();
+        for path in paths {
+            for segment in path.windows(2) {
+                let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+                for x in x1.min(x2)..=x1.max(x2) {
+                    for y in y1.min(y2)..=y1.max(y2) {
+                        tiles.insert((x, y), Tile::Rock);
+                    }
+                }
+            }
+            if let [point] = path[..] {
+                tiles.insert(point, Tile::Rock);
+            }
+        }
+        let depth = tiles.keys().map(|&(_, y)| y).max().unwrap_or(SOURCE.1);
+
+        Self {
+            tiles,
+            depth,
+            source: SOURCE,
+            bottom: Bottom::Abyss,
+            resting: 0,
+            path: vec![],
+        }
+    }
+
+    /// Pours the sand in at `source` instead, resting sand stays where it is.
+    pub fn with_source(mut self, source: Point) -> Self {
+        self.source = source;
+        self.path.clear();
+        self
+    }
+
+    pub fn with_bottom(mut self, bottom: Bottom) -> Self {
+        self.bottom = bottom;
+        self.path.clear();
+        self
+    }
+
+    /// Number of grains of sand that came to rest so far.
+    pub fn resting(&self) -> usize {
+        self.resting
+    }
+
+    fn blocked(&self, (x, y): Point) -> bool {
+        match self.bottom {
+            Bottom::Floor if y >= self.depth + 2 => true,
+            _ => self.tiles.contains_key(&(x, y)),
+        }
+    }
+
+    /// Lets one grain of sand fall and returns where it came to rest, `None`
+    /// once grains fall into the abyss or the source is blocked.
+    pub fn step(&mut self) -> Option<Point> {
+        if self.path.is_empty() {
+            if self.blocked(self.source) {
+                return None;
+            }
+            self.path.push(self.source);
+        }
+
+        loop {
+            let &(x, y) = self.path.last()?;
+            let next = [(x, y + 1), (x - 1, y + 1), (x + 1, y + 1)]
+                .into_iter()
+                .find(|&p| !self.blocked(p));
+            match next {
+                Some((_, y)) if self.bottom == Bottom::Abyss && y > self.depth => return None,
+                Some(next) => self.path.push(next),
+                None => {
+                    self.path.pop();
+                    self.tiles.insert((x, y), Tile::Sand);
+                    self.resting += 1;
+                    return Some((x, y));
+                }
+            }
+        }
+    }
+
+    /// Lets sand fall until no more comes to rest and returns how much rests.
+    pub fn fill(&mut self) -> usize {
+        while self.step().is_some() {}
+        self.resting
+    }
+}
+
+/// Draws rock as `#`, sand as `o` and the source as `+` within the bounding
+/// box of everything drawn, including the floor below it.
+impl fmt::Display for Cave {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let points = || self.tiles.keys().chain(std::iter::once(&self.source));
+        let min_x = points().map(|&(x, _)| x).min().unwrap_or(self.source.0);
+        let max_x = points().map(|&(x, _)| x).max().unwrap_or(self.source.0);
+        let min_y = points().map(|&(_, y)| y).min().unwrap_or(self.source.1);
+        let max_y = match self.bottom {
+            Bottom::Abyss => points().map(|&(_, y)| y).max().unwrap_or(self.source.1),
+            Bottom::Floor => self.depth + 2,
+        };
+
+        for y in min_y..=max_y {
+            for x in min_x..=max_x {
+                let c = match self.tiles.get(&(x, y)) {
+                    Some(Tile::Rock) => '#',
+                    Some(Tile::Sand) => 'o',
+                    None if (x, y) == self.source => '+',
+                    None if self.blocked((x, y)) => '#',
+                    None => '.',
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_line(idx: usize, line: &str) -> Result<Vec<Point>, ParseError> {
+    let path = line
+        .split(" -> ")
+        .map(|point| {
+            let (x, y) = point
+                .split_once(',')
+                .ok_or_else(|| ParseError::at(14, idx, line, point, "expected a point `x,y`"))?;
+            let coordinate = |c: &str| {
+                c.parse::<u16>()
+                    .map(i32::from)
+                    .map_err(|_| ParseError::at(14, idx, line, c, "expected a coordinate"))
+            };
+            Ok((coordinate(x)?, coordinate(y)?))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if path
+        .windows(2)
+        .any(|s| s[0].0 != s[1].0 && s[0].1 != s[1].1)
+    {
+        return Err(ParseError::line(14, idx, line, "diagonal rock path"));
+    }
+    Ok(path)
+}
+
+#[aoc_generator(day14)]
+pub fn input_gen(input: &str) -> Result<Cave, ParseError> {
+    let input = normalize(input);
+    let paths = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_line(idx, line))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Cave::new(&paths))
+}
+
+#[aoc(day14, part1)]
+pub fn solve_part1(input: &Cave) -> usize {
+    input.clone().with_bottom(Bottom::Abyss).fill()
+}
+
+#[aoc(day14, part2)]
+pub fn solve_part2(input: &Cave) -> usize {
+    input.clone().with_bottom(Bottom::Floor).fill()
+}
+
+/// One frame per grain of sand that comes to rest, in the abyss for part 1
+/// and on the floor for part 2.
+pub fn frames(cave: Cave, part: Part) -> impl Iterator<Item = String> {
+    let mut cave = cave.with_bottom(match part {
+        Part::One => Bottom::Abyss,
+        Part::Two => Bottom::Floor,
+    });
+    std::iter::from_fn(move || {
+        let (x, y) = cave.step()?;
+        Some(format!(
+            "grain {} rests at {x},{y}\n\n{cave}",
+            cave.resting()
+        ))
+    })
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Cave;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{input_gen, solve_part1, solve_part2, Bottom};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
+
+    const INPUT: &str = include_str!("../fixtures/day14/example_1.txt");
+
+    #[test]
+    fn test_part1() -> anyhow::Result<()> {
+        assert_eq!(solve_part1(&input_gen(INPUT)?), 24);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> anyhow::Result<()> {
+        assert_eq!(solve_part2(&input_gen(INPUT)?), 93);
+        Ok(())
+    }
+
+    #[test]
+    fn test_steps() -> anyhow::Result<()> {
+        let mut cave = input_gen(INPUT)?;
+        assert_eq!(cave.step(), Some((500, 8)));
+        assert_eq!(cave.step(), Some((499, 8)));
+        for _ in 0..3 {
+            cave.step();
+        }
+        assert_eq!(cave.resting(), 5);
+        assert_eq!(
+            cave.to_string(),
+            "......+...\n\
+             ..........\n\
+             ..........\n\
+             ..........\n\
+             ....#...##\n\
+             ....#...#.\n\
+             ..###...#.\n\
+             ......o.#.\n\
+             ....oooo#.\n\
+             #########.\n"
+        );
+
+        let mut cave = cave.with_source((495, 0)).with_bottom(Bottom::Floor);
+        assert_eq!(cave.step(), Some((495, 8)));
+        assert_eq!(cave.fill(), 90);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("498,4 -> 498,6 -> 496,6\n503,4 -> 502,x").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(14, 2, 14, "x", "expected a coordinate")
+        );
+        let err = input_gen("498,4 -> 497,6").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(14, 1, 1, "498,4 -> 497,6", "diagonal rock path")
+        );
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), 24);
+        assert_eq!(solve_part2(&parsed), 93);
+        Ok(())
+    }
+}
@@ -0,0 +1,258 @@
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::interval::IntervalSet;
+use crate::solution::{Answer, Solution};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::{BTreeSet, HashSet};
+use std::ops::RangeInclusive;
+
+pub type Point = (i64, i64);
+
+/// The row part 1 asks about.
+pub const ROW: i64 = 2_000_000;
+/// The distress beacon is within `0..=MAX` in both directions.
+pub const MAX: i64 = 4_000_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sensor {
+    position: Point,
+    beacon: Point,
+}
+
+fn distance(a: Point, b: Point) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+impl Sensor {
+    /// Distance to the closest beacon, there is no other beacon within it.
+    pub fn radius(&self) -> i64 {
+        distance(self.position, self.beacon)
+    }
+
+    pub fn covers(&self, point: Point) -> bool {
+        distance(self.position, point) <= self.radius()
+    }
+
+    /// The cells of row `y` within the radius, if any.
+    pub fn reach(&self, y: i64) -> Option<RangeInclusive<i64>> {
+        let (x, sensor_y) = self.position;
+        let width = self.radius() - (sensor_y - y).abs();
+        (width >= 0).then(|| x - width..=x + width)
+    }
+}
+
+fn parse_line(idx: usize, line: &str) -> Result<Sensor, ParseError> {
+    let (sensor, beacon) = line
+        .strip_prefix("Sensor at ")
+        .and_then(|rest| rest.split_once(": closest beacon is at "))
+        .ok_or_else(|| {
+            ParseError::line(
+                15,
+                idx,
+                line,
+                "expected `Sensor at <point>: closest beacon is at <point>`",
+            )
+        })?;
+    let point = |point: &str| {
+        let (x, y) = point
+            .split_once(", ")
+            .ok_or_else(|| ParseError::at(15, idx, line, point, "expected `x=<x>, y=<y>`"))?;
+        let coordinate = |c: &str, prefix| {
+            c.strip_prefix(prefix)
+                .and_then(|n| n.parse::<i32>().ok())
+                .map(i64::from)
+                .ok_or_else(|| ParseError::at(15, idx, line, c, format!("expected `{prefix}<n>`")))
+        };
+        Ok::<_, ParseError>((coordinate(x, "x=")?, coordinate(y, "y=")?))
+    };
+
+    Ok(Sensor {
+        position: point(sensor)?,
+        beacon: point(beacon)?,
+    })
+}
+
+#[aoc_generator(day15)]
+pub fn input_gen(input: &str) -> Result<Vec<Sensor>, ParseError> {
+    let input = normalize(input);
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_line(idx, line))
+        .collect()
+}
+
+/// The cells of row `y` within reach of any sensor.
+pub fn coverage(sensors: &[Sensor], y: i64) -> IntervalSet {
+    sensors.iter().filter_map(|s| s.reach(y)).collect()
+}
+
+/// Number of cells in row `y` that cannot hold a beacon.
+pub fn ruled_out(sensors: &[Sensor], y: i64) -> u64 {
+    let beacons = sensors
+        .iter()
+        .filter(|s| s.beacon.1 == y)
+        .map(|s| s.beacon.0)
+        .collect::<HashSet<_>>();
+    coverage(sensors, y).len() - beacons.len() as u64
+}
+
+/// The only cell within `0..=max` in both directions that no sensor covers.
+///
+/// Only crossings of the diagonal lines just beyond the reach of the sensors
+/// are checked. The neighbours `(x - 1, y - 1)` and `(x + 1, y + 1)` of the
+/// cell are either outside the square or covered, else they'd be uncovered
+/// too. So unless the cell is a corner, it's one or two steps beyond the
+/// reach of some sensor on a line `x + y = sum`, and by the same argument on
+/// a line `x - y = diff`. Usually the cell sits in a one wide gap between
+/// sensors, whose lines are tried first.
+pub fn find_beacon(sensors: &[Sensor], max: i64) -> Option<Point> {
+    // lines x + y = sum and x - y = diff `offset` beyond the reach of each
+    // sensor, below and above it
+    let lines = |offset: i64| {
+        let mut sums = [BTreeSet::new(), BTreeSet::new()];
+        let mut diffs = [BTreeSet::new(), BTreeSet::new()];
+        for sensor in sensors {
+            let ((x, y), r) = (sensor.position, sensor.radius() + offset);
+            sums[0].insert(x + y - r);
+            sums[1].insert(x + y + r);
+            diffs[0].insert(x - y - r);
+            diffs[1].insert(x - y + r);
+        }
+        (sums, diffs)
+    };
+    let uncovered = |&(x, y): &Point| {
+        (0..=max).contains(&x) && (0..=max).contains(&y) && !covered(sensors, (x, y))
+    };
+    let crossings = |sums: &BTreeSet<i64>, diffs: &BTreeSet<i64>| {
+        sums.iter()
+            .flat_map(|&sum| diffs.iter().map(move |&diff| (sum, diff)))
+            .filter(|(sum, diff)| (sum + diff) % 2 == 0)
+            .map(|(sum, diff)| ((sum + diff) / 2, (sum - diff) / 2))
+            .find(uncovered)
+    };
+
+    let (sums, diffs) = lines(1);
+    let gaps = |lines: &[BTreeSet<i64>; 2]| &lines[0] & &lines[1];
+    if let Some(point) = crossings(&gaps(&sums), &gaps(&diffs)) {
+        return Some(point);
+    }
+
+    let (far_sums, far_diffs) = lines(2);
+    let all = |near: &[BTreeSet<i64>; 2], far: &[BTreeSet<i64>; 2]| {
+        near.iter().chain(far).flatten().copied().collect()
+    };
+    crossings(&all(&sums, &far_sums), &all(&diffs, &far_diffs)).or_else(|| {
+        [(0, 0), (max, 0), (0, max), (max, max)]
+            .into_iter()
+            .find(uncovered)
+    })
+}
+
+fn covered(sensors: &[Sensor], point: Point) -> bool {
+    sensors.iter().any(|s| s.covers(point))
+}
+
+#[aoc(day15, part1)]
+pub fn solve_part1(input: &[Sensor]) -> u64 {
+    ruled_out(input, ROW)
+}
+
+#[aoc(day15, part2)]
+pub fn solve_part2(input: &[Sensor]) -> Option<i64> {
+    find_beacon(input, MAX).map(|(x, y)| x * 4_000_000 + y)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = Vec<Sensor>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        solve_part2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{coverage, find_beacon, input_gen, ruled_out};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
+
+    // the example asks about row 10 and a square up to 20 instead of the
+    // real input's ones, so it's not in the generated fixture tests
+    const INPUT: &str = include_str!("../fixtures/day15/example_1.txt");
+
+    #[test]
+    fn test_part1() -> anyhow::Result<()> {
+        let sensors = input_gen(INPUT)?;
+        assert_eq!(coverage(&sensors, 10).ranges(), [-2..=24]);
+        assert_eq!(ruled_out(&sensors, 10), 26);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> anyhow::Result<()> {
+        let sensors = input_gen(INPUT)?;
+        assert_eq!(find_beacon(&sensors, 20), Some((14, 11)));
+        assert_eq!(coverage(&sensors, 11).gaps(0..=20).ranges(), [14..=14]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_beacon() -> anyhow::Result<()> {
+        // (1, 2) is the only cell in 0..=6 out of reach, but it's two cells
+        // beyond two of the sensors along x - y
+        let sensors = input_gen(
+            "Sensor at x=0, y=1: closest beacon is at x=0, y=0\n\
+             Sensor at x=2, y=6: closest beacon is at x=2, y=2\n\
+             Sensor at x=0, y=6: closest beacon is at x=0, y=3\n\
+             Sensor at x=6, y=0: closest beacon is at x=6, y=5",
+        )?;
+        assert_eq!(find_beacon(&sensors, 6), Some((1, 2)));
+
+        // only the corner (0, 6) is out of reach
+        let sensors = input_gen(
+            "Sensor at x=6, y=5: closest beacon is at x=6, y=3\n\
+             Sensor at x=4, y=2: closest beacon is at x=4, y=6\n\
+             Sensor at x=2, y=2: closest beacon is at x=2, y=7",
+        )?;
+        assert_eq!(find_beacon(&sensors, 6), Some((0, 6)));
+        assert_eq!(find_beacon(&sensors, 5), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("Sensor at x=2, y=18: closest beacon is at x=-2, z=15").unwrap_err();
+        assert_eq!(err, ParseError::new(15, 1, 49, "z=15", "expected `y=<n>`"));
+        let err = input_gen("Sensor at x=2, y=18").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                15,
+                1,
+                1,
+                "Sensor at x=2, y=18",
+                "expected `Sensor at <point>: closest beacon is at <point>`"
+            )
+        );
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let sensors = input_gen(&windows_style(INPUT))?;
+        assert_eq!(ruled_out(&sensors, 10), 26);
+        assert_eq!(find_beacon(&sensors, 20), Some((14, 11)));
+        Ok(())
+    }
+}
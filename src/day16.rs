@@ -0,0 +1,302 @@
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::HashMap;
+
+/// The valve everyone starts at.
+pub const START: &str = "AA";
+/// Most valves with a flow rate a network may have, the solver keeps a table
+/// entry for every set of them.
+pub const MAX_VALVES: usize = 20;
+
+/// The valves worth opening and how long it takes to walk between them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    /// Flow rates of the valves worth opening.
+    rates: Vec<u32>,
+    /// Minutes from one valve to another, the start comes after the valves.
+    distances: Vec<Vec<u32>>,
+}
+
+/// All subsets of `mask`, from `mask` itself down to the empty set.
+fn subsets(mask: usize) -> impl Iterator<Item = usize> {
+    std::iter::successors(Some(mask), move |&s| (s > 0).then(|| (s - 1) & mask))
+}
+
+impl Network {
+    /// Most pressure `actors` can release together in `minutes`.
+    ///
+    /// Every actor starts at [`START`] and each valve is opened by one of
+    /// them, so the actors split the valves between them.
+    pub fn max_pressure(&self, minutes: u32, actors: usize) -> u64 {
+        if actors == 0 {
+            return 0;
+        }
+        let best = self.best_per_set(minutes);
+        let all = best.len() - 1;
+
+        // the most one actor releases opening only valves in the set, no
+        // matter which of them
+        let mut within = best.clone();
+        for bit in 0..self.rates.len() {
+            for mask in 0..=all {
+                if mask & 1 << bit != 0 {
+                    within[mask] = within[mask].max(within[mask ^ 1 << bit]);
+                }
+            }
+        }
+
+        // one more actor takes some of the set, the others the rest
+        let split = |mask, within: &[u64]| {
+            subsets(mask)
+                .map(|s| best[s] + within[mask ^ s])
+                .max()
+                .unwrap_or(0)
+        };
+        for _ in 2..actors {
+            within = (0..=all).map(|mask| split(mask, &within)).collect();
+        }
+        match actors {
+            1 => within[all],
+            _ => split(all, &within),
+        }
+    }
+
+    /// The most a single actor releases in `minutes` by opening exactly the
+    /// valves of each set, zero for sets it cannot open in time.
+    fn best_per_set(&self, minutes: u32) -> Vec<u64> {
+        let mut best = vec![0; 1 << self.rates.len()];
+        self.visit(self.rates.len(), minutes, 0, 0, &mut best);
+        best
+    }
+
+    fn visit(&self, at: usize, left: u32, open: usize, released: u64, best: &mut [u64]) {
+        best[open] = best[open].max(released);
+        for (next, rate) in self.rates.iter().enumerate() {
+            // walk there and open it
+            let cost = self.distances[at][next].saturating_add(1);
+            if open & 1 << next == 0 && cost < left {
+                let left = left - cost;
+                // any rate that parses times any time left fits a u64
+                let released = released + u64::from(*rate) * u64::from(left);
+                self.visit(next, left, open | 1 << next, released, best);
+            }
+        }
+    }
+}
+
+struct Valve<'a> {
+    idx: usize,
+    line: &'a str,
+    name: &'a str,
+    rate: u32,
+    tunnels: Vec<&'a str>,
+}
+
+fn parse_line(idx: usize, line: &str) -> Result<Valve<'_>, ParseError> {
+    let format = || {
+        ParseError::line(
+            16,
+            idx,
+            line,
+            "expected `Valve <name> has flow rate=<rate>; tunnels lead to valves <names>`",
+        )
+    };
+    let (name, rest) = line
+        .strip_prefix("Valve ")
+        .and_then(|rest| rest.split_once(" has flow rate="))
+        .ok_or_else(format)?;
+    let (rate, tunnels) = rest.split_once("; ").ok_or_else(format)?;
+    let tunnels = tunnels
+        .strip_prefix("tunnels lead to valves ")
+        .or_else(|| tunnels.strip_prefix("tunnel leads to valve "))
+        .ok_or_else(format)?;
+
+    let tunnels = tunnels.split(", ").collect::<Vec<_>>();
+    for name in tunnels.iter().chain([&name]) {
+        if name.len() != 2 || !name.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err(ParseError::at(
+                16,
+                idx,
+                line,
+                name,
+                "expected two capital letters",
+            ));
+        }
+    }
+
+    Ok(Valve {
+        idx,
+        line,
+        name,
+        rate: rate
+            .parse()
+            .map_err(|_| ParseError::at(16, idx, line, rate, "expected a flow rate"))?,
+        tunnels,
+    })
+}
+
+#[aoc_generator(day16)]
+pub fn input_gen(input: &str) -> Result<Network, ParseError> {
+    let input = normalize(input);
+    let valves = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| parse_line(idx, line))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut ids = HashMap::new();
+    for (i, valve) in valves.iter().enumerate() {
+        if ids.insert(valve.name, i).is_some() {
+            return Err(ParseError::at(
+                16,
+                valve.idx,
+                valve.line,
+                valve.name,
+                "duplicate valve",
+            ));
+        }
+    }
+    let n = valves.len();
+    let mut distances = vec![vec![u32::MAX; n]; n];
+    for (i, valve) in valves.iter().enumerate() {
+        distances[i][i] = 0;
+        for tunnel in &valve.tunnels {
+            let j = *ids.get(tunnel).ok_or_else(|| {
+                ParseError::at(16, valve.idx, valve.line, tunnel, "unknown valve")
+            })?;
+            distances[i][j] = distances[i][j].min(1);
+        }
+    }
+    // Floyd-Warshall
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                let via = distances[i][k].saturating_add(distances[k][j]);
+                distances[i][j] = distances[i][j].min(via);
+            }
+        }
+    }
+
+    let start = *ids.get(START).ok_or_else(|| {
+        let (idx, line) = input.lines().enumerate().last().unwrap_or((0, ""));
+        ParseError::line(16, idx, line, format!("missing valve {START}"))
+    })?;
+    let useful = (0..n).filter(|&i| valves[i].rate > 0).collect::<Vec<_>>();
+    if let Some(&i) = useful.get(MAX_VALVES) {
+        return Err(ParseError::line(
+            16,
+            valves[i].idx,
+            valves[i].line,
+            format!("more than {MAX_VALVES} valves with a flow rate"),
+        ));
+    }
+    let kept = useful.iter().chain([&start]);
+    Ok(Network {
+        rates: useful.iter().map(|&i| valves[i].rate).collect(),
+        distances: kept
+            .clone()
+            .map(|&i| kept.clone().map(|&j| distances[i][j]).collect())
+            .collect(),
+    })
+}
+
+#[aoc(day16, part1)]
+pub fn solve_part1(input: &Network) -> u64 {
+    input.max_pressure(30, 1)
+}
+
+#[aoc(day16, part2)]
+pub fn solve_part2(input: &Network) -> u64 {
+    // teaching the elephant takes 4 minutes
+    input.max_pressure(26, 2)
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = Network;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{input_gen, solve_part1, solve_part2};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
+
+    const INPUT: &str = include_str!("../fixtures/day16/example_1.txt");
+
+    #[test]
+    fn test_part1() -> anyhow::Result<()> {
+        assert_eq!(solve_part1(&input_gen(INPUT)?), 1651);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> anyhow::Result<()> {
+        assert_eq!(solve_part2(&input_gen(INPUT)?), 1707);
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_pressure() -> anyhow::Result<()> {
+        let network = input_gen(INPUT)?;
+        assert_eq!(network.rates.len(), 6);
+        assert_eq!(network.max_pressure(0, 1), 0);
+        assert_eq!(network.max_pressure(3, 1), 20);
+        assert_eq!(network.max_pressure(30, 0), 0);
+        let pressures = (1..=4).map(|actors| network.max_pressure(26, actors));
+        assert_eq!(pressures.collect::<Vec<_>>(), [1327, 1707, 1794, 1825]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_huge_rates() -> anyhow::Result<()> {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+                     Valve BB has flow rate=4294967295; tunnel leads to valve AA\n\
+                     Valve CC has flow rate=4294967295; tunnel leads to valve AA\n";
+        let parsed = input_gen(input)?;
+        assert_eq!(solve_part1(&parsed), 4294967295 * (28 + 25));
+        assert_eq!(solve_part2(&parsed), 4294967295 * (24 + 24));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("Valve AA has flow rate=0; tunnels lead to valves DD, BB").unwrap_err();
+        assert_eq!(err, ParseError::new(16, 1, 50, "DD", "unknown valve"));
+        let err = input_gen("Valve BB has flow rate=13; tunnel leads to valve BB").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                16,
+                1,
+                1,
+                "Valve BB has flow rate=13; tunnel leads to valve BB",
+                "missing valve AA"
+            )
+        );
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), 1651);
+        assert_eq!(solve_part2(&parsed), 1707);
+        Ok(())
+    }
+}
@@ -0,0 +1,319 @@
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::HashMap;
+
+/// The rocks of the puzzle in the order they fall, drawn top to bottom.
+pub const SHAPES: [&[&str]; 5] = [
+    &["####"],
+    &[".#.", "###", ".#."],
+    &["..#", "..#", "###"],
+    &["#", "#", "#", "#"],
+    &["##", "##"],
+];
+
+/// How deep below the top of the tower the surface is compared when looking
+/// for a cycle, deeper columns count as equally deep.
+const PROFILE_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+/// A rock, as one bitmask per row with bit `x` for column `x`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    /// Bottom row first.
+    rows: Vec<u32>,
+    width: usize,
+}
+
+impl Shape {
+    /// The shape drawn with `#` in `picture`, top row first.
+    ///
+    /// Panics if the picture is empty or wider than 32 columns.
+    pub fn new(picture: &[&str]) -> Self {
+        let width = picture.iter().map(|row| row.len()).max().unwrap_or(0);
+        assert!((1..=32).contains(&width), "shapes are 1 to 32 wide");
+        let rows = picture
+            .iter()
+            .rev()
+            .map(|row| {
+                row.bytes()
+                    .enumerate()
+                    .filter(|&(_, c)| c == b'#')
+                    .fold(0, |mask, (x, _)| mask | 1 << x)
+            })
+            .collect();
+        Self { rows, width }
+    }
+}
+
+/// A chamber rocks fall into, pushed around by jets of hot gas.
+#[derive(Debug, Clone)]
+pub struct Chamber {
+    width: usize,
+    shapes: Vec<Shape>,
+    jets: Vec<Jet>,
+    /// Bottom row first, with bit `x` for column `x`.
+    rows: Vec<u32>,
+    next_shape: usize,
+    next_jet: usize,
+    rocks: u64,
+}
+
+impl Chamber {
+    /// An empty chamber seven units wide, with the rocks of [`SHAPES`] pushed
+    /// around by `jets`.
+    ///
+    /// Panics if there are no jets.
+    pub fn new(jets: Vec<Jet>) -> Self {
+        let shapes = SHAPES.iter().map(|picture| Shape::new(picture)).collect();
+        Self::custom(7, shapes, jets)
+    }
+
+    /// An empty chamber `width` units wide, with `shapes` falling in turn
+    /// and pushed around by `jets`.
+    ///
+    /// Panics if there are no shapes or jets, if the chamber is wider than 32
+    /// or if it's narrower than a shape plus the two units rocks appear from
+    /// the left wall.
+    pub fn custom(width: usize, shapes: Vec<Shape>, jets: Vec<Jet>) -> Self {
+        assert!(!jets.is_empty(), "no jets");
+        assert!(!shapes.is_empty(), "no shapes");
+        assert!(width <= 32, "chambers are at most 32 wide");
+        assert!(
+            shapes.iter().all(|s| s.width + 2 <= width),
+            "shape too wide for the chamber"
+        );
+        Self {
+            width,
+            shapes,
+            jets,
+            rows: vec![],
+            next_shape: 0,
+            next_jet: 0,
+            rocks: 0,
+        }
+    }
+
+    /// Height of the tower.
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Number of rocks that came to rest.
+    pub fn rocks(&self) -> u64 {
+        self.rocks
+    }
+
+    fn fits(&self, shape: &Shape, x: usize, y: usize) -> bool {
+        x + shape.width <= self.width
+            && shape.rows.iter().enumerate().all(|(i, row)| {
+                let occupied = self.rows.get(y + i).copied().unwrap_or(0);
+                occupied & row << x == 0
+            })
+    }
+
+    /// Lets the next rock fall until it comes to rest.
+    pub fn drop_rock(&mut self) {
+        let shape = &self.shapes[self.next_shape];
+        self.next_shape = (self.next_shape + 1) % self.shapes.len();
+        let (mut x, mut y) = (2, self.height() + 3);
+        loop {
+            let jet = self.jets[self.next_jet];
+            self.next_jet = (self.next_jet + 1) % self.jets.len();
+            match jet {
+                Jet::Left if x > 0 && self.fits(shape, x - 1, y) => x -= 1,
+                Jet::Right if self.fits(shape, x + 1, y) => x += 1,
+                _ => {}
+            }
+            if y == 0 || !self.fits(shape, x, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+
+        let shape = shape.rows.clone();
+        for (i, row) in shape.into_iter().enumerate() {
+            if y + i == self.rows.len() {
+                self.rows.push(0);
+            }
+            self.rows[y + i] |= row << x;
+        }
+        self.rocks += 1;
+    }
+
+    /// Distance of every column's top block from the top of the tower.
+    fn profile(&self) -> Vec<usize> {
+        (0..self.width)
+            .map(|x| {
+                self.rows
+                    .iter()
+                    .rev()
+                    .take(PROFILE_DEPTH)
+                    .position(|row| row & 1 << x != 0)
+                    .unwrap_or(PROFILE_DEPTH)
+            })
+            .collect()
+    }
+
+    /// Height of the tower once `rocks` rocks came to rest in total.
+    ///
+    /// Once the next shape and jet and the surface of the tower are the same
+    /// as after an earlier rock, everything in between repeats. So whole
+    /// cycles are skipped and only what's left is simulated.
+    pub fn height_after(mut self, rocks: u64) -> u64 {
+        let mut seen = HashMap::new();
+        let mut skipped = 0;
+        while self.rocks < rocks {
+            self.drop_rock();
+            if skipped > 0 {
+                continue;
+            }
+            let state = (self.next_shape, self.next_jet, self.profile());
+            let now = (self.rocks, self.height() as u64);
+            if let Some((before, height)) = seen.insert(state, now) {
+                let cycles = (rocks - self.rocks) / (self.rocks - before);
+                self.rocks += cycles * (self.rocks - before);
+                skipped = cycles * (now.1 - height);
+            }
+        }
+        self.height() as u64 + skipped
+    }
+}
+
+#[aoc_generator(day17)]
+pub fn input_gen(input: &str) -> Result<Vec<Jet>, ParseError> {
+    let input = normalize(input);
+    let mut lines = input.lines().enumerate();
+    let Some((_, pattern)) = lines.next() else {
+        return Err(ParseError::line(17, 0, "", "expected a jet pattern"));
+    };
+    if let Some((idx, line)) = lines.next() {
+        return Err(ParseError::line(17, idx, line, "expected a single line"));
+    }
+    if pattern.is_empty() {
+        return Err(ParseError::line(17, 0, pattern, "expected a jet pattern"));
+    }
+
+    pattern
+        .char_indices()
+        .map(|(pos, c)| match c {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            _ => Err(ParseError::at(
+                17,
+                0,
+                pattern,
+                &pattern[pos..pos + c.len_utf8()],
+                "expected < or >",
+            )),
+        })
+        .collect()
+}
+
+#[aoc(day17, part1)]
+pub fn solve_part1(input: &[Jet]) -> u64 {
+    Chamber::new(input.to_vec()).height_after(2022)
+}
+
+#[aoc(day17, part2)]
+pub fn solve_part2(input: &[Jet]) -> u64 {
+    Chamber::new(input.to_vec()).height_after(1_000_000_000_000)
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = Vec<Jet>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{input_gen, solve_part1, solve_part2, Chamber, Jet, Shape, SHAPES};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
+
+    const INPUT: &str = include_str!("../fixtures/day17/example_1.txt");
+
+    #[test]
+    fn test_part1() -> anyhow::Result<()> {
+        assert_eq!(solve_part1(&input_gen(INPUT)?), 3068);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> anyhow::Result<()> {
+        assert_eq!(solve_part2(&input_gen(INPUT)?), 1514285714288);
+        Ok(())
+    }
+
+    #[test]
+    fn test_chamber() -> anyhow::Result<()> {
+        let mut chamber = Chamber::new(input_gen(INPUT)?);
+        for height in [1, 4, 6, 7, 9] {
+            chamber.drop_rock();
+            assert_eq!(chamber.height(), height);
+        }
+        assert_eq!(chamber.rocks(), 5);
+        // skipping cycles gives the same height as dropping every rock
+        let mut slow = chamber.clone();
+        for _ in 5..5000 {
+            slow.drop_rock();
+        }
+        assert_eq!(chamber.height_after(5000), slow.height() as u64);
+
+        // pushed to the left wall, pebbles stack up in a single column
+        let pebbles = Chamber::custom(3, vec![Shape::new(&["#"])], vec![Jet::Left]);
+        assert_eq!(pebbles.height_after(1_000_000_000_000), 1_000_000_000_000);
+
+        let shapes = vec![Shape::new(&["##"]), Shape::new(&["#.", "##"])];
+        let chamber = Chamber::custom(5, shapes, input_gen(INPUT)?);
+        let mut slow = chamber.clone();
+        for _ in 0..5000 {
+            slow.drop_rock();
+        }
+        assert_eq!(chamber.height_after(5000), slow.height() as u64);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic = "shape too wide for the chamber"]
+    fn test_too_narrow() {
+        let shapes = SHAPES.iter().map(|picture| Shape::new(picture)).collect();
+        Chamber::custom(5, shapes, vec![Jet::Left]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen(">>><<>x<").unwrap_err();
+        assert_eq!(err, ParseError::new(17, 1, 7, "x", "expected < or >"));
+        let err = input_gen("").unwrap_err();
+        assert_eq!(err, ParseError::new(17, 1, 1, "", "expected a jet pattern"));
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), 3068);
+        assert_eq!(solve_part2(&parsed), 1514285714288);
+        Ok(())
+    }
+}
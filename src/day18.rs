@@ -0,0 +1,163 @@
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
+use crate::voxel::{Bounds, Voxel, VoxelSet};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::VecDeque;
+
+fn parse_line(idx: usize, line: &str) -> Result<Voxel, ParseError> {
+    let mut coordinates = line.split(',').map(|c| {
+        c.parse::<i16>()
+            .map(i32::from)
+            .map_err(|_| ParseError::at(18, idx, line, c, "expected a coordinate"))
+    });
+    let mut next = || {
+        coordinates
+            .next()
+            .ok_or_else(|| ParseError::line(18, idx, line, "expected `x,y,z`"))?
+    };
+    let cube = Voxel::new(next()?, next()?, next()?);
+    if coordinates.next().is_some() {
+        return Err(ParseError::line(18, idx, line, "expected `x,y,z`"));
+    }
+    Ok(cube)
+}
+
+/// Most cubes of space the droplet's bounding box may hold, part 2 visits
+/// all of them.
+pub const MAX_VOLUME: u64 = 10_000_000;
+
+#[aoc_generator(day18)]
+pub fn input_gen(input: &str) -> Result<VoxelSet, ParseError> {
+    let input = normalize(input);
+    let mut droplet = VoxelSet::new();
+    let mut bounds: Option<Bounds> = None;
+    for (idx, line) in input.lines().enumerate() {
+        let cube = parse_line(idx, line)?;
+        if !droplet.insert(cube) {
+            return Err(ParseError::line(18, idx, line, "duplicate cube"));
+        }
+        let grown = bounds.map_or(
+            Bounds {
+                min: cube,
+                max: cube,
+            },
+            |b| b.include(cube),
+        );
+        if grown.volume() > MAX_VOLUME {
+            let reason = format!("droplet spans more than {MAX_VOLUME} cubes of space");
+            return Err(ParseError::line(18, idx, line, reason));
+        }
+        bounds = Some(grown);
+    }
+    Ok(droplet)
+}
+
+/// Area of the faces of `droplet` that can be reached from outside, without
+/// those of air pockets trapped inside.
+///
+/// Flood fills the air in a box one larger than the droplet, starting at a
+/// corner, and counts every face the air runs into.
+pub fn exterior_area(droplet: &VoxelSet) -> usize {
+    let Some(bounds) = droplet.bounding_box().map(|b| b.grow(1)) else {
+        return 0;
+    };
+    let mut outside = VoxelSet::from_iter([bounds.min]);
+    let mut queue = VecDeque::from([bounds.min]);
+    let mut faces = 0;
+    while let Some(air) = queue.pop_front() {
+        for n in air.neighbours().filter(|&n| bounds.contains(n)) {
+            if droplet.contains(n) {
+                faces += 1;
+            } else if outside.insert(n) {
+                queue.push_back(n);
+            }
+        }
+    }
+    faces
+}
+
+#[aoc(day18, part1)]
+pub fn solve_part1(input: &VoxelSet) -> usize {
+    input.surface_area()
+}
+
+#[aoc(day18, part2)]
+pub fn solve_part2(input: &VoxelSet) -> usize {
+    exterior_area(input)
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Input = VoxelSet;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        Some(solve_part2(input).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{exterior_area, input_gen, solve_part1, solve_part2, MAX_VOLUME};
+    use crate::error::ParseError;
+    use crate::input::windows_style;
+    use crate::voxel::{Voxel, VoxelSet};
+
+    const INPUT: &str = include_str!("../fixtures/day18/example_1.txt");
+
+    #[test]
+    fn test_part1() -> anyhow::Result<()> {
+        assert_eq!(solve_part1(&input_gen(INPUT)?), 64);
+        Ok(())
+    }
+
+    #[test]
+    fn test_part2() -> anyhow::Result<()> {
+        assert_eq!(solve_part2(&input_gen(INPUT)?), 58);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hollow_cube() {
+        // a 3x3x3 cube without its center
+        let shell = (0..27)
+            .map(|i| Voxel::new(i % 3, i / 3 % 3, i / 9))
+            .filter(|&v| v != Voxel::new(1, 1, 1))
+            .collect::<VoxelSet>();
+        assert_eq!(shell.surface_area(), 54 + 6);
+        assert_eq!(exterior_area(&shell), 54);
+        assert_eq!(shell.components().len(), 1);
+        assert_eq!(exterior_area(&VoxelSet::new()), 0);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = input_gen("2,2,2\n1,2,x").unwrap_err();
+        assert_eq!(err, ParseError::new(18, 2, 5, "x", "expected a coordinate"));
+        let err = input_gen("2,2,2\n1,2").unwrap_err();
+        assert_eq!(err, ParseError::new(18, 2, 1, "1,2", "expected `x,y,z`"));
+        let err = input_gen("2,2,2\n2,2,2").unwrap_err();
+        assert_eq!(err, ParseError::new(18, 2, 1, "2,2,2", "duplicate cube"));
+        let err = input_gen("-32768,-32768,-32768\n32767,32767,32767").unwrap_err();
+        let reason = format!("droplet spans more than {MAX_VOLUME} cubes of space");
+        assert_eq!(err, ParseError::new(18, 2, 1, "32767,32767,32767", reason));
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), 64);
+        assert_eq!(solve_part2(&parsed), 58);
+        Ok(())
+    }
+}
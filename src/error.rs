@@ -0,0 +1,91 @@
+use std::fmt;
+
+/// An error produced while parsing a day's puzzle input.
+///
+/// Lines and columns are 1-based and columns count `char`s, so they can be
+/// shown to users as is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(
+        day: u8,
+        line: usize,
+        column: usize,
+        text: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            day,
+            line,
+            column,
+            text: text.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Creates an error pointing at `token` within `line`, where `line_idx` is
+    /// the 0-based index as produced by `lines().enumerate()`.
+    ///
+    /// `token` is expected to be a subslice of `line`, if it is not the error
+    /// points at the start of the line.
+    pub fn at(
+        day: u8,
+        line_idx: usize,
+        line: &str,
+        token: &str,
+        reason: impl Into<String>,
+    ) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|&offset| offset + token.len() <= line.len())
+            .unwrap_or(0);
+        let column = line[..offset].chars().count() + 1;
+        Self::new(day, line_idx + 1, column, token, reason)
+    }
+
+    /// Creates an error covering the whole of `line`.
+    pub fn line(day: u8, line_idx: usize, line: &str, reason: impl Into<String>) -> Self {
+        Self::new(day, line_idx + 1, 1, line, reason)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02}, line {}, column {}: {} (found `{}`)",
+            self.day, self.line, self.column, self.reason, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseError;
+
+    #[test]
+    fn test_at() {
+        let line = "move 1 from x to 3";
+        let err = ParseError::at(5, 2, line, &line[12..13], "expected a number");
+        assert_eq!(err, ParseError::new(5, 3, 13, "x", "expected a number"));
+        assert_eq!(
+            err.to_string(),
+            "day 05, line 3, column 13: expected a number (found `x`)"
+        );
+    }
+
+    #[test]
+    fn test_at_foreign_token() {
+        let err = ParseError::at(1, 0, "abc", "xyz", "oops");
+        assert_eq!(err.column, 1);
+    }
+}
@@ -0,0 +1,18 @@
+//! Tests generated from the puzzle examples in `fixtures/`.
+//!
+//! Every `fixtures/dayNN/example_K.txt` is solved and its answers compared
+//! with the `part1` and `part2` values of the `[example_K]` table in
+//! `fixtures/dayNN/expected.toml`, a missing value skips that part. Adding an
+//! example needs no code, `build.rs` picks it up. A malformed fixture is
+//! reported when the tests are compiled, other builds don't see it.
+
+use crate::solution::{self, Part};
+
+fn check(day: u8, input: &str, part: Part, expected: &str) {
+    let day = solution::day(day).expect("fixture for a day without a solution");
+    let parsed = day.parse(input).unwrap_or_else(|err| panic!("{err}"));
+    let answer = day.solve(&parsed, part).map(|answer| answer.to_string());
+    assert_eq!(answer.as_deref(), Some(expected));
+}
+
+include!(concat!(env!("OUT_DIR"), "/fixtures.rs"));
@@ -0,0 +1,809 @@
+//! Seeded generators for synthetic puzzle inputs.
+//!
+//! Every generator produces text in the exact format the day's parser
+//! accepts, scaled by `size` (elves, lines, side length, monkeys, ...). Where
+//! an answer can be derived from the construction or with a deliberately
+//! simple computation it is returned alongside the input.
+
+use crate::solution::Answer;
+use std::{collections::HashSet, fmt::Write, ops::RangeInclusive};
+
+/// SplitMix64, small and fully specified so a seed produces the same input
+/// on every platform and with every dependency version.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, `n` must not be zero.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let len = (range.end() - range.start()) as u64 + 1;
+        range.start() + (self.next_u64() % len) as i64
+    }
+
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+
+    pub fn letter(&mut self, letters: RangeInclusive<u8>) -> char {
+        char::from(self.range(i64::from(*letters.start())..=i64::from(*letters.end())) as u8)
+    }
+}
+
+/// A synthetic puzzle input, with the answers if they are known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Generated {
+    fn new(input: String) -> Self {
+        Self {
+            input,
+            part1: None,
+            part2: None,
+        }
+    }
+
+    fn with_answers(input: String, part1: impl Into<Answer>, part2: impl Into<Answer>) -> Self {
+        Self {
+            input,
+            part1: Some(part1.into()),
+            part2: Some(part2.into()),
+        }
+    }
+}
+
+/// A day's generator, with the size its benchmark generates inputs at.
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    generate: fn(&mut Rng, usize) -> Generated,
+    /// Large enough for the quadratic paths of the solver to show.
+    pub bench_size: usize,
+}
+
+impl Generator {
+    const fn new(generate: fn(&mut Rng, usize) -> Generated, bench_size: usize) -> Self {
+        Self {
+            generate,
+            bench_size,
+        }
+    }
+
+    /// Generates an input, the same `seed` and `size` always produce the same
+    /// input.
+    pub fn generate(&self, seed: u64, size: usize) -> Generated {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+}
+
+/// Generators of all days, in order.
+pub static GENERATORS: [Generator; 18] = [
+    Generator::new(day01, 10_000),
+    Generator::new(day02, 30_000),
+    Generator::new(day03, 10_000),
+    Generator::new(day04, 30_000),
+    Generator::new(day05, 100_000),
+    Generator::new(day06, 1_000_000),
+    Generator::new(day07, 2_000),
+    Generator::new(day08, 300),
+    Generator::new(day09, 10_000),
+    Generator::new(day10, 10_000),
+    Generator::new(day11, 100),
+    Generator::new(day12, 100),
+    Generator::new(day13, 5_000),
+    Generator::new(day14, 300),
+    Generator::new(day15, 30),
+    Generator::new(day16, 60),
+    Generator::new(day17, 10_000),
+    Generator::new(day18, 30),
+];
+
+/// Looks up the generator of `day` in [`GENERATORS`].
+pub fn generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.get(usize::from(day).checked_sub(1)?)
+}
+
+/// Generates an input for `day`, the same `seed` and `size` always produce the
+/// same input.
+pub fn generate(day: u8, seed: u64, size: usize) -> Option<Generated> {
+    Some(generator(day)?.generate(seed, size))
+}
+
+/// `size` elves carrying up to 15 snacks each.
+pub fn day01(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    let mut totals = vec![];
+    for elf in 0..size.max(1) {
+        if elf > 0 {
+            input.push('\n');
+        }
+        let mut total = 0;
+        for _ in 0..=rng.below(15) {
+            let calories = 1 + rng.below(60_000);
+            total += calories;
+            writeln!(input, "{calories}").unwrap();
+        }
+        totals.push(total);
+    }
+
+    totals.sort_unstable_by(|a, b| b.cmp(a));
+    let top_three: usize = totals.iter().take(3).sum();
+    Generated::with_answers(input, totals[0], top_three)
+}
+
+/// `size` rounds of rock paper scissors.
+pub fn day02(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    let (mut part1, mut part2) = (0, 0);
+    for _ in 0..size.max(1) {
+        let (other, second) = (rng.below(3), rng.below(3));
+        writeln!(
+            input,
+            "{} {}",
+            ["A", "B", "C"][other],
+            ["X", "Y", "Z"][second]
+        )
+        .unwrap();
+
+        // 0 is a draw, 1 a win and 2 a loss for us
+        let result = (second + 3 - other) % 3;
+        part1 += second + 1 + [3, 6, 0][result];
+
+        let ours = (other + second + 2) % 3;
+        part2 += ours + 1 + 3 * second;
+    }
+    Generated::with_answers(input, part1, part2)
+}
+
+fn priority_to_item(priority: usize) -> char {
+    match priority {
+        1..=26 => char::from(b'a' + priority as u8 - 1),
+        _ => char::from(b'A' + priority as u8 - 27),
+    }
+}
+
+/// `size` groups of three rucksacks.
+///
+/// Every group draws its badge, the three misplaced items and the rest of
+/// the items from disjoint pools, so the answers are known by construction.
+pub fn day03(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    let (mut part1, mut part2) = (0, 0);
+    for _ in 0..size.max(1) {
+        let mut priorities: Vec<usize> = (1..=52).collect();
+        rng.shuffle(&mut priorities);
+        let (badge, rest) = priorities.split_first().unwrap();
+        let (misplaced, pools) = rest.split_at(3);
+        part2 += badge;
+
+        for (&misplaced, pool) in misplaced.iter().zip(pools.chunks(16)) {
+            part1 += misplaced;
+            let len = 2 + rng.below(14);
+            let badge_in_first = rng.one_in(2);
+            for (half, with_badge) in pool.chunks(8).zip([badge_in_first, !badge_in_first]) {
+                let mut items = vec![misplaced];
+                if with_badge {
+                    items.push(*badge);
+                }
+                while items.len() < len {
+                    items.push(*rng.pick(half));
+                }
+                rng.shuffle(&mut items);
+                input.extend(items.into_iter().map(priority_to_item));
+            }
+            input.push('\n');
+        }
+    }
+    Generated::with_answers(input, part1, part2)
+}
+
+/// `size` pairs of section assignments.
+pub fn day04(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    let (mut part1, mut part2) = (0_usize, 0_usize);
+    for _ in 0..size.max(1) {
+        let mut range = || {
+            let (a, b) = (rng.range(1..=99), rng.range(1..=99));
+            a.min(b)..=a.max(b)
+        };
+        let (r1, r2) = (range(), range());
+        writeln!(
+            input,
+            "{}-{},{}-{}",
+            r1.start(),
+            r1.end(),
+            r2.start(),
+            r2.end()
+        )
+        .unwrap();
+
+        if r1.clone().all(|s| r2.contains(&s)) || r2.clone().all(|s| r1.contains(&s)) {
+            part1 += 1;
+        }
+        if r1.clone().any(|s| r2.contains(&s)) {
+            part2 += 1;
+        }
+    }
+    Generated::with_answers(input, part1, part2)
+}
+
+/// Up to nine stacks and `size` moves, none of which empties a stack.
+pub fn day05(rng: &mut Rng, size: usize) -> Generated {
+    let num_stacks = 2 + rng.below(8);
+    let mut stacks: Vec<Vec<char>> = (0..num_stacks)
+        .map(|i| {
+            let height = if i == 0 { 2 } else { 1 } + rng.below(30);
+            (0..height).map(|_| rng.letter(b'A'..=b'Z')).collect()
+        })
+        .collect();
+
+    let mut input = String::new();
+    let max_height = stacks.iter().map(Vec::len).max().unwrap();
+    for level in (0..max_height).rev() {
+        let row: Vec<_> = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(c) => format!("[{c}]"),
+                None => "   ".to_owned(),
+            })
+            .collect();
+        writeln!(input, "{}", row.join(" ")).unwrap();
+    }
+    let labels: Vec<_> = (1..=num_stacks).map(|i| format!(" {i} ")).collect();
+    writeln!(input, "{}\n", labels.join(" ")).unwrap();
+
+    let mut part2_stacks = stacks.clone();
+    for _ in 0..size {
+        let from = loop {
+            let from = rng.below(num_stacks);
+            if stacks[from].len() >= 2 {
+                break from;
+            }
+        };
+        let to = (from + 1 + rng.below(num_stacks - 1)) % num_stacks;
+        let count = 1 + rng.below(stacks[from].len() - 1);
+        writeln!(input, "move {count} from {} to {}", from + 1, to + 1).unwrap();
+
+        for _ in 0..count {
+            let c = stacks[from].pop().unwrap();
+            stacks[to].push(c);
+        }
+        let at = part2_stacks[from].len() - count;
+        let moved = part2_stacks[from].split_off(at);
+        part2_stacks[to].extend(moved);
+    }
+
+    let tops = |stacks: &[Vec<char>]| {
+        stacks
+            .iter()
+            .map(|s| *s.last().unwrap())
+            .collect::<String>()
+    };
+    Generated::with_answers(input, tops(&stacks), tops(&part2_stacks))
+}
+
+fn first_marker(stream: &[u8], n: usize) -> usize {
+    (n..=stream.len())
+        .find(|&end| stream[end - n..end].iter().collect::<HashSet<_>>().len() == n)
+        .unwrap()
+}
+
+/// A stream of `size` letters from a small alphabet, followed by fourteen
+/// distinct letters so both markers exist.
+pub fn day06(rng: &mut Rng, size: usize) -> Generated {
+    let alphabet = 3 + rng.below(11) as u8;
+    let mut stream: Vec<u8> = (0..size)
+        .map(|_| rng.letter(b'a'..=b'a' + alphabet - 1) as u8)
+        .collect();
+    let mut tail: Vec<u8> = (b'a'..=b'z').collect();
+    rng.shuffle(&mut tail);
+    stream.extend(&tail[..14]);
+
+    let part1 = first_marker(&stream, 4);
+    let part2 = first_marker(&stream, 14);
+    Generated::with_answers(String::from_utf8(stream).unwrap(), part1, part2)
+}
+
+fn word(rng: &mut Rng) -> String {
+    (0..1 + rng.below(8))
+        .map(|_| rng.letter(b'a'..=b'z'))
+        .collect()
+}
+
+/// A terminal session exploring a tree of `size` directories, with a total
+/// size that leaves between 1 and 25 million free.
+pub fn day07(rng: &mut Rng, size: usize) -> Generated {
+    struct Dir {
+        name: String,
+        depth: usize,
+        children: Vec<usize>,
+        files: Vec<(String, u64)>,
+    }
+
+    let mut dirs = vec![Dir {
+        name: "/".to_owned(),
+        depth: 0,
+        children: vec![],
+        files: vec![],
+    }];
+    for i in 1..size.max(1) {
+        let parent = loop {
+            let parent = rng.below(dirs.len());
+            if dirs[parent].depth < 20 {
+                break parent;
+            }
+        };
+        dirs[parent].children.push(i);
+        dirs.push(Dir {
+            name: format!("{}{i}", word(rng)),
+            depth: dirs[parent].depth + 1,
+            children: vec![],
+            files: vec![],
+        });
+    }
+
+    // distribute the total size over all files by weight
+    let mut weights = vec![];
+    for (i, dir) in dirs.iter_mut().enumerate() {
+        for j in 0..usize::from(i == 0) + rng.below(5) {
+            let ext = ["", ".txt", ".dat", ".log"][rng.below(4)];
+            dir.files.push((format!("{}{j}{ext}", word(rng)), 0));
+            weights.push(1 + rng.below(1000) as u64);
+        }
+    }
+    let total = 45_000_000 + rng.below(24_000_000) as u64;
+    let weight_sum: u64 = weights.iter().sum();
+    let mut weights = weights.into_iter();
+    for dir in &mut dirs {
+        for (_, file_size) in &mut dir.files {
+            *file_size = (weights.next().unwrap() * total / weight_sum).max(1);
+        }
+    }
+
+    fn emit(dirs: &[Dir], i: usize, rng: &mut Rng, input: &mut String) {
+        input.push_str("$ ls\n");
+        let mut entries: Vec<String> = dirs[i]
+            .files
+            .iter()
+            .map(|(name, size)| format!("{size} {name}"))
+            .chain(
+                dirs[i]
+                    .children
+                    .iter()
+                    .map(|&c| format!("dir {}", dirs[c].name)),
+            )
+            .collect();
+        rng.shuffle(&mut entries);
+        for entry in entries {
+            writeln!(input, "{entry}").unwrap();
+        }
+        for &child in &dirs[i].children {
+            writeln!(input, "$ cd {}", dirs[child].name).unwrap();
+            emit(dirs, child, rng, input);
+            input.push_str("$ cd ..\n");
+        }
+    }
+    let mut input = String::from("$ cd /\n");
+    emit(&dirs, 0, rng, &mut input);
+
+    // children always come after their parent
+    let mut sizes: Vec<u64> = dirs
+        .iter()
+        .map(|d| d.files.iter().map(|f| f.1).sum())
+        .collect();
+    for i in (1..dirs.len()).rev() {
+        let parent = (0..i).find(|&p| dirs[p].children.contains(&i)).unwrap();
+        sizes[parent] += sizes[i];
+    }
+    let needed = 30_000_000 - (70_000_000 - sizes[0]);
+    let part1: u64 = sizes[1..].iter().filter(|&&s| s <= 100_000).sum();
+    let part2 = sizes[1..].iter().filter(|&&s| s >= needed).min().copied();
+
+    Generated {
+        input,
+        part1: Some(part1.into()),
+        part2: part2.map(Answer::from),
+    }
+}
+
+/// A forest `size` trees wide and half as high.
+pub fn day08(rng: &mut Rng, size: usize) -> Generated {
+    let width = size.max(1);
+    let input = (0..width / 2 + 1)
+        .map(|_| {
+            (0..width)
+                .map(|_| rng.letter(b'0'..=b'9'))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Generated::new(input)
+}
+
+/// `size` random rope motions.
+pub fn day09(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        writeln!(
+            input,
+            "{} {}",
+            rng.pick(&["L", "R", "U", "D"]),
+            1 + rng.below(20)
+        )
+        .unwrap();
+    }
+    Generated::new(input)
+}
+
+/// A program of `size` instructions.
+pub fn day10(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    // the value of X during every cycle, starting with cycle 1
+    let mut during = vec![];
+    let mut x = 1;
+    for _ in 0..size {
+        if rng.one_in(3) {
+            input.push_str("noop\n");
+            during.push(x);
+        } else {
+            let v = rng.range(-10..=10);
+            writeln!(input, "addx {v}").unwrap();
+            during.extend([x, x]);
+            x += v;
+        }
+    }
+    during.resize(during.len().max(240), x);
+
+    let part1: i64 = (20..=220)
+        .step_by(40)
+        .map(|c| c * during[c as usize - 1])
+        .sum();
+    let mut screen = String::new();
+    for row in during[..240].chunks(40) {
+        for (col, x) in row.iter().enumerate() {
+            screen.push(if (x - 1..=x + 1).contains(&(col as i64)) {
+                '#'
+            } else {
+                '.'
+            });
+        }
+        screen.push('\n');
+    }
+
+    Generated {
+        input,
+        part1: Some(part1.into()),
+        part2: Some(Answer::Text(screen)),
+    }
+}
+
+/// Notes on `size` monkeys.
+///
+/// Every monkey but the first throws to monkeys before it, so an item is
+/// inspected at most twice per round. Together with small factors this
+/// keeps the worry levels of part 1 within a `u64` even for huge troops.
+pub fn day11(rng: &mut Rng, size: usize) -> Generated {
+    const PRIMES: [u32; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+    let count = size.max(2);
+    let mut input = String::new();
+    for i in 0..count {
+        let items: Vec<_> = (0..=rng.below(5))
+            .map(|_| (1 + rng.below(99)).to_string())
+            .collect();
+        let op = match rng.below(3) {
+            0 => format!("+ {}", 1 + rng.below(8)),
+            1 => format!("* {}", 2 + rng.below(6)),
+            _ => "+ old".to_owned(),
+        };
+        let mut target = || match i {
+            0 => 1 + rng.below(count - 1),
+            _ => rng.below(i),
+        };
+        let (success, failure) = (target(), target());
+
+        if i > 0 {
+            input.push('\n');
+        }
+        writeln!(input, "Monkey {i}:").unwrap();
+        writeln!(input, "  Starting items: {}", items.join(", ")).unwrap();
+        writeln!(input, "  Operation: new = old {op}").unwrap();
+        writeln!(input, "  Test: divisible by {}", rng.pick(&PRIMES)).unwrap();
+        writeln!(input, "    If true: throw to monkey {success}").unwrap();
+        writeln!(input, "    If false: throw to monkey {failure}").unwrap();
+    }
+    Generated::new(input)
+}
+
+/// A `size` by `size` height map of noise, with a gentle path from `S` to `E`
+/// carved into it so the summit is always reachable.
+pub fn day12(rng: &mut Rng, size: usize) -> Generated {
+    // the path needs at least 26 squares to climb from a to z
+    let n = size.max(26);
+    let mut grid: Vec<Vec<char>> = (0..n)
+        .map(|_| (0..n).map(|_| rng.letter(b'a'..=b'z')).collect())
+        .collect();
+
+    let (mut x, mut y) = (rng.below(n / 4), rng.below(n / 4));
+    let (end_x, end_y) = (n - 1 - rng.below(n / 4), n - 1 - rng.below(n / 4));
+    let len = (end_x - x) + (end_y - y);
+    grid[y][x] = 'S';
+    for step in 1..=len {
+        if y == end_y || (x < end_x && rng.one_in(2)) {
+            x += 1;
+        } else {
+            y += 1;
+        }
+        grid[y][x] = char::from(b'a' + (25 * step / len) as u8);
+    }
+    grid[end_y][end_x] = 'E';
+
+    let input = grid
+        .into_iter()
+        .map(String::from_iter)
+        .collect::<Vec<_>>()
+        .join("\n");
+    Generated::new(input)
+}
+
+fn packet(rng: &mut Rng, depth: usize, input: &mut String) {
+    input.push('[');
+    for i in 0..rng.below(5) {
+        if i > 0 {
+            input.push(',');
+        }
+        if depth > 0 && rng.one_in(3) {
+            packet(rng, depth - 1, input);
+        } else {
+            write!(input, "{}", rng.below(11)).unwrap();
+        }
+    }
+    input.push(']');
+}
+
+/// `size` pairs of packets, the first of which is nested `size` lists deep, or
+/// as deep as [`MAX_DEPTH`](crate::day13::MAX_DEPTH) allows.
+pub fn day13(rng: &mut Rng, size: usize) -> Generated {
+    let depth = size.clamp(1, crate::day13::MAX_DEPTH);
+    let mut input = String::new();
+    for side in [1, 2] {
+        input += &"[".repeat(depth);
+        write!(input, "{side}").unwrap();
+        input += &"]".repeat(depth);
+        input.push('\n');
+    }
+
+    for _ in 1..size {
+        input.push('\n');
+        for _ in 0..2 {
+            packet(rng, 6, &mut input);
+            input.push('\n');
+        }
+    }
+    Generated::new(input)
+}
+
+/// `size` rock paths below the source, each a shelf, a wall or a cup, with
+/// some of them repeated like in the real inputs. A wide cup below them all
+/// catches the sand, so not all of it is lost to the abyss in part 1.
+pub fn day14(rng: &mut Rng, size: usize) -> Generated {
+    let spread = 5 + size as i64 / 3;
+    let (left, right, bottom) = (500 - spread - 3, 500 + spread + 9, 2 * spread + 14);
+    let mut lines = vec![format!(
+        "{left},{} -> {left},{bottom} -> {right},{bottom} -> {right},{}",
+        bottom - spread,
+        bottom - spread
+    )];
+    for _ in 1..size.max(1) {
+        if !lines.is_empty() && rng.one_in(4) {
+            let line = rng.pick(&lines).clone();
+            lines.push(line);
+            continue;
+        }
+        let x = 500 + rng.range(-spread..=spread);
+        let y = 2 + rng.range(0..=2 * spread);
+        let (w, h) = (rng.range(1..=6), rng.range(1..=6));
+        let points = match rng.below(3) {
+            0 => vec![(x, y), (x + w, y)],
+            1 => vec![(x, y), (x, y + h)],
+            _ => vec![(x, y), (x, y + h), (x + w, y + h), (x + w, y)],
+        };
+        let points: Vec<_> = points.iter().map(|(x, y)| format!("{x},{y}")).collect();
+        lines.push(points.join(" -> "));
+    }
+    Generated::new(lines.join("\n"))
+}
+
+/// `size` sensor reports leaving exactly one cell of the square uncovered.
+///
+/// Four sensors diagonally around the distress beacon each reach over a
+/// whole quadrant of the square but not the beacon itself. The others are
+/// random and keep their distance from it.
+pub fn day15(rng: &mut Rng, size: usize) -> Generated {
+    const MAX: i64 = 4_000_000;
+    let (x, y) = (rng.range(0..=MAX), rng.range(0..=MAX));
+    let mut lines = vec![];
+    for (dx, dy) in [(-1, -1), (1, -1), (-1, 1), (1, 1)] {
+        let sensor = (x + dx * MAX, y + dy * MAX);
+        let beacon = (sensor.0 - dx * (2 * MAX - 1), sensor.1);
+        lines.push((sensor, beacon));
+    }
+    while lines.len() < size {
+        let sensor = (rng.range(0..=MAX), rng.range(0..=MAX));
+        let distance = (sensor.0 - x).abs() + (sensor.1 - y).abs();
+        if distance < 2 {
+            continue;
+        }
+        let radius = rng.range(1..=(distance - 1).min(MAX / 2));
+        let dx = rng.range(-radius..=radius);
+        let dy = (radius - dx.abs()) * if rng.one_in(2) { 1 } else { -1 };
+        lines.push((sensor, (sensor.0 + dx, sensor.1 + dy)));
+    }
+    rng.shuffle(&mut lines);
+
+    let mut input = String::new();
+    for ((sx, sy), (bx, by)) in lines {
+        writeln!(
+            input,
+            "Sensor at x={sx}, y={sy}: closest beacon is at x={bx}, y={by}"
+        )
+        .unwrap();
+    }
+    Generated {
+        part2: Some(Answer::from(x * 4_000_000 + y)),
+        ..Generated::new(input)
+    }
+}
+
+/// A connected network of `size` valves, up to 15 of which have a flow rate,
+/// a third of them at most.
+pub fn day16(rng: &mut Rng, size: usize) -> Generated {
+    let n = size.clamp(2, 26 * 26);
+    let mut names: Vec<String> = (b'A'..=b'Z')
+        .flat_map(|a| (b'A'..=b'Z').map(move |b| String::from_utf8(vec![a, b]).unwrap()))
+        .filter(|name| name != "AA")
+        .collect();
+    rng.shuffle(&mut names);
+    names.insert(0, "AA".to_owned());
+    names.truncate(n);
+
+    // long corridors branching off each other, with a few shortcuts so there
+    // are loops
+    let mut tunnels = vec![vec![]; n];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for i in 1..n {
+        connect(i, i - 1 - rng.below(i.min(3)));
+    }
+    for _ in 0..n / 10 {
+        connect(rng.below(n), rng.below(n));
+    }
+
+    let mut rates = vec![0; n];
+    let mut useful: Vec<usize> = (1..n).collect();
+    rng.shuffle(&mut useful);
+    for &i in useful.iter().take(15.min(n / 3)) {
+        rates[i] = 1 + rng.below(25);
+    }
+
+    let mut input = String::new();
+    for i in 0..n {
+        let others = tunnels[i].iter().map(|&j| names[j].as_str());
+        let others = others.collect::<Vec<_>>().join(", ");
+        let lead = match tunnels[i].len() {
+            1 => "tunnel leads to valve",
+            _ => "tunnels lead to valves",
+        };
+        writeln!(
+            input,
+            "Valve {} has flow rate={}; {lead} {others}",
+            names[i], rates[i]
+        )
+        .unwrap();
+    }
+    Generated::new(input)
+}
+
+/// A jet pattern of `size` runs of one to four jets in the same direction.
+pub fn day17(rng: &mut Rng, size: usize) -> Generated {
+    let mut input = String::new();
+    for _ in 0..size.max(1) {
+        let jet = *rng.pick(&['<', '>']);
+        input.extend(std::iter::repeat_n(jet, 1 + rng.below(4)));
+    }
+    Generated::new(input)
+}
+
+/// A ball of lava `size` cubes across, with holes that leave air pockets
+/// inside and dents in its surface.
+pub fn day18(rng: &mut Rng, size: usize) -> Generated {
+    let n = size.clamp(3, 60) as i64;
+    let mut cubes = vec![];
+    for x in 0..n {
+        for y in 0..n {
+            for z in 0..n {
+                let d = [x, y, z].map(|c| 2 * c + 1 - n);
+                if d.iter().map(|d| d * d).sum::<i64>() <= n * n && !rng.one_in(6) {
+                    cubes.push(format!("{x},{y},{z}"));
+                }
+            }
+        }
+    }
+    rng.shuffle(&mut cubes);
+    Generated::new(cubes.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate, GENERATORS};
+    use crate::solution::{Part, DAYS};
+
+    #[test]
+    fn test_deterministic() {
+        for day in &DAYS {
+            assert_eq!(generate(day.day, 7, 30), generate(day.day, 7, 30));
+        }
+        assert_ne!(generate(1, 7, 30), generate(1, 8, 30));
+        assert!(generate(DAYS.len() as u8 + 1, 7, 30).is_none());
+    }
+
+    #[test]
+    fn test_answers() -> anyhow::Result<()> {
+        assert_eq!(GENERATORS.len(), DAYS.len());
+        for day in &DAYS {
+            for seed in 0..5 {
+                let generated = generate(day.day, seed, 30).unwrap();
+                let parsed = day.parse(&generated.input)?;
+                for (part, expected) in Part::ALL
+                    .into_iter()
+                    .zip([generated.part1, generated.part2])
+                {
+                    if let Some(expected) = expected {
+                        let actual = day.solve(&parsed, part);
+                        assert_eq!(
+                            actual,
+                            Some(expected),
+                            "day {} part {part} seed {seed}",
+                            day.day
+                        );
+                    } else {
+                        day.solve(&parsed, part);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+use crate::error::ParseError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A cell position, `x` counts columns from the left and `y` rows from the
+/// top.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pos {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Moves by `(dx, dy)`, `None` if that leaves the non-negative quadrant.
+    pub fn offset(self, (dx, dy): (isize, isize)) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+/// Steps to the four orthogonal neighbours: up, down, left, right.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+
+/// Steps to all eight neighbours, orthogonal ones first.
+pub const ADJACENT: [(isize, isize); 8] = [
+    (0, -1),
+    (0, 1),
+    (-1, 0),
+    (1, 0),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+/// A rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row-major order.
+    ///
+    /// Panics if `cells` doesn't hold exactly `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "grid size mismatch");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one cell per character, every line is a row.
+    ///
+    /// `cell` gets the position and character of each cell and returns
+    /// either its value or the reason it's invalid. Lines of differing length
+    /// are an error too, reported for `day`.
+    pub fn parse<F>(day: u8, input: &str, mut cell: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(Pos, char) -> Result<T, &'static str>,
+    {
+        let height = input.lines().count();
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, line) in input.lines().enumerate() {
+            if line.chars().count() != width {
+                return Err(ParseError::line(
+                    day,
+                    y,
+                    line,
+                    format!("expected a row of {width} cells"),
+                ));
+            }
+            for (x, (pos, c)) in line.char_indices().enumerate() {
+                let value = cell(Pos::new(x, y), c).map_err(|reason| {
+                    ParseError::at(day, y, line, &line[pos..pos + c.len_utf8()], reason)
+                })?;
+                cells.push(value);
+            }
+        }
+
+        Ok(Self::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.y * self.width + pos.x])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| Pos::new(i % width, i / width))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        (0..self.height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    /// The cells reached by repeatedly stepping by `step` from `from`,
+    /// excluding `from` itself, up to the edge of the grid.
+    pub fn ray(&self, from: Pos, step: (isize, isize)) -> impl Iterator<Item = (Pos, &T)> {
+        std::iter::successors(Some(from), move |pos| pos.offset(step))
+            .skip(1)
+            .map_while(|pos| Some((pos, self.get(pos)?)))
+    }
+
+    /// Neighbours of `pos` along `steps` that lie inside the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: Pos,
+        steps: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        steps
+            .iter()
+            .filter_map(move |&step| pos.offset(step))
+            .filter(|&pos| self.contains(pos))
+    }
+
+    /// The up to four orthogonal neighbours of `pos`.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &ORTHOGONAL)
+    }
+
+    /// The up to eight neighbours of `pos`, including diagonal ones.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &ADJACENT)
+    }
+
+    /// Mirrors the grid along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} is outside the grid"))
+    }
+}
+
+/// Prints each row on its own line, cells are not separated.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, Pos, ORTHOGONAL};
+    use crate::error::ParseError;
+
+    fn digits(input: &str) -> Grid<u32> {
+        Grid::parse(0, input, |_, c| c.to_digit(10).ok_or("expected a digit")).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits("123\n456");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Pos::new(2, 0)], 3);
+        assert_eq!(grid[Pos::new(0, 1)], 4);
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456\n");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Grid::parse(8, "12\n3x", |_, c| c.to_digit(10).ok_or("expected a digit"));
+        assert_eq!(err, Err(ParseError::new(8, 2, 2, "x", "expected a digit")));
+        let err = Grid::parse(8, "12\n345", |_, c| {
+            c.to_digit(10).ok_or("expected a digit")
+        });
+        assert_eq!(
+            err,
+            Err(ParseError::new(8, 2, 1, "345", "expected a row of 2 cells"))
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = digits("123\n456\n789");
+        let values = |pos: Vec<Pos>| pos.into_iter().map(|p| grid[p]).collect::<Vec<_>>();
+        assert_eq!(values(grid.neighbours4(Pos::new(0, 0)).collect()), [4, 2]);
+        assert_eq!(
+            values(grid.neighbours4(Pos::new(1, 1)).collect()),
+            [2, 8, 4, 6]
+        );
+        assert_eq!(grid.neighbours8(Pos::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Pos::new(2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = digits("123\n456\n789");
+        assert!(grid.row(1).copied().eq([4, 5, 6]));
+        assert!(grid.column(2).rev().copied().eq([9, 6, 3]));
+        let rays: Vec<Vec<u32>> = ORTHOGONAL
+            .iter()
+            .map(|&step| grid.ray(Pos::new(1, 1), step).map(|(_, &v)| v).collect())
+            .collect();
+        assert_eq!(rays, [vec![2], vec![8], vec![4], vec![6]]);
+        assert!(grid.ray(Pos::new(0, 0), (1, 1)).map(|(_, &v)| v).eq([5, 9]));
+    }
+
+    #[test]
+    fn test_transpose() {
+        let grid = digits("123\n456");
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36\n");
+        assert_eq!(grid.transpose().transpose(), grid);
+    }
+}
@@ -0,0 +1,132 @@
+use std::borrow::Cow;
+use std::io::{self, BufRead};
+
+/// Brings puzzle input into the canonical form all generators expect.
+///
+/// Strips a leading byte order mark, turns `\r\n` into `\n`, removes trailing
+/// whitespace from every line and drops trailing blank lines. Leading
+/// whitespace is significant (e.g. the stack drawing of day 5) and is kept.
+/// Line numbers are preserved, so parse errors still point into the original
+/// input.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+
+    if !input.contains('\r')
+        && !input.ends_with(char::is_whitespace)
+        && input
+            .lines()
+            .all(|line| line.len() == line.trim_end().len())
+    {
+        return Cow::Borrowed(input);
+    }
+
+    let mut normalized = String::with_capacity(input.len());
+    for line in input.lines() {
+        normalized.push_str(line.trim_end());
+        normalized.push('\n');
+    }
+    normalized.truncate(normalized.trim_end().len());
+    Cow::Owned(normalized)
+}
+
+/// Streaming counterpart of [`normalize`], reads `reader` line by line.
+///
+/// Yields the 0-based line index along with each line. Blank lines are held
+/// back until a non-blank line follows, so trailing blank lines are dropped
+/// just like [`normalize`] does without reading the whole input first.
+pub fn lines<R: BufRead>(reader: R) -> Lines<R> {
+    Lines {
+        reader,
+        idx: 0,
+        blank: 0,
+        buf: String::new(),
+    }
+}
+
+pub struct Lines<R> {
+    reader: R,
+    idx: usize,
+    /// Number of blank lines read but not yet yielded.
+    blank: usize,
+    buf: String,
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<(usize, String)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.blank > 0 && !self.buf.is_empty() {
+                self.blank -= 1;
+                self.idx += 1;
+                return Some(Ok((self.idx - 1, String::new())));
+            }
+            if !self.buf.is_empty() {
+                let line = std::mem::take(&mut self.buf);
+                self.idx += 1;
+                return Some(Ok((self.idx - 1, line)));
+            }
+
+            match self.reader.read_line(&mut self.buf) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(err) => return Some(Err(err)),
+            }
+            if self.idx == 0 && self.blank == 0 {
+                if let Some(line) = self.buf.strip_prefix('\u{feff}') {
+                    self.buf = line.to_owned();
+                }
+            }
+            self.buf.truncate(self.buf.trim_end().len());
+            if self.buf.is_empty() {
+                self.blank += 1;
+            }
+        }
+    }
+}
+
+/// Turns a puzzle example into the messiest input [`normalize`] should accept:
+/// a byte order mark, `\r\n` line endings, trailing spaces and a trailing
+/// blank line.
+#[cfg(test)]
+pub fn windows_style(input: &str) -> String {
+    let mut messy = String::from('\u{feff}');
+    for line in input.lines() {
+        messy.push_str(line);
+        messy.push_str("  \r\n");
+    }
+    messy.push_str("\r\n");
+    messy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lines, normalize, windows_style};
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_normalize() {
+        let input = "    [D]\n[N] [C]\n\nmove 1 from 2 to 1";
+        assert_eq!(normalize(&windows_style(input)), input);
+    }
+
+    #[test]
+    fn test_normalize_borrows() {
+        let input = "  a\n\nb";
+        assert!(matches!(normalize(input), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_lines() {
+        for input in ["  a\n\n\nb", "\na\n\nb  \n", "", "\n\n"] {
+            let messy = windows_style(input);
+            let streamed: Vec<_> = lines(messy.as_bytes()).map(Result::unwrap).collect();
+            let expected: Vec<_> = normalize(input)
+                .lines()
+                .map(str::to_owned)
+                .enumerate()
+                .collect();
+            assert_eq!(streamed, expected, "{input:?}");
+        }
+    }
+}
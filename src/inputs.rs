@@ -0,0 +1,228 @@
+//! Puzzle inputs, read from a local cache and downloaded on a miss.
+//!
+//! Inputs live in `inputs/2022/dayNN.txt`. A missing one is fetched from
+//! `<base url>/day/<day>/input` with the session cookie of a logged in user
+//! and written to the cache, so every input is downloaded at most once.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Environment variable holding the value of the `session` cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable overriding [`DEFAULT_BASE_URL`].
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+/// Environment variable with contact details for the user agent, e.g. an
+/// email address, so the site can reach whoever runs the fetcher.
+pub const CONTACT_VAR: &str = "AOC_CONTACT";
+
+pub const DEFAULT_DIR: &str = "inputs/2022";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2022";
+
+/// Resolves puzzle inputs, see the module documentation.
+#[derive(Debug)]
+pub struct Inputs {
+    /// Directory of the cached inputs.
+    pub dir: PathBuf,
+    /// URL the `/day/<day>/input` paths are appended to.
+    pub base_url: String,
+    /// Session token, downloads fail without one.
+    pub session: Option<String>,
+    pub user_agent: String,
+    /// Least time between the start of two downloads.
+    pub min_interval: Duration,
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl Inputs {
+    /// Inputs cached in `dir`, downloaded as configured by the environment.
+    pub fn from_env(dir: impl Into<PathBuf>) -> Self {
+        let var = |name| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let mut user_agent = format!("aoc-2022/{}", env!("CARGO_PKG_VERSION"));
+        if let Some(contact) = var(CONTACT_VAR) {
+            user_agent += &format!(" ({})", contact.trim());
+        }
+        Self {
+            dir: dir.into(),
+            base_url: var(BASE_URL_VAR).unwrap_or_else(|| DEFAULT_BASE_URL.into()),
+            session: var(SESSION_VAR).map(|s| s.trim().to_owned()),
+            user_agent,
+            min_interval: Duration::from_secs(3),
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Where the input of `day` is cached.
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{day:02}.txt"))
+    }
+
+    /// The path of the input of `day`, downloading it first if it is not
+    /// cached yet.
+    pub fn fetch(&self, day: u8) -> anyhow::Result<PathBuf> {
+        let path = self.path(day);
+        if !path.exists() {
+            let input = self.download(day, &path)?;
+            std::fs::create_dir_all(&self.dir)
+                .map_err(|err| anyhow::anyhow!("cannot create {}: {err}", self.dir.display()))?;
+            // write next to the cache and rename, so an interrupted download
+            // never leaves a truncated input behind
+            let partial = path.with_extension("txt.part");
+            std::fs::write(&partial, input)
+                .and_then(|()| std::fs::rename(&partial, &path))
+                .map_err(|err| anyhow::anyhow!("cannot write {}: {err}", path.display()))?;
+        }
+        Ok(path)
+    }
+
+    /// The input of `day`, downloading it first if it is not cached yet.
+    pub fn get(&self, day: u8) -> anyhow::Result<String> {
+        let path = self.fetch(day)?;
+        std::fs::read_to_string(&path)
+            .map_err(|err| anyhow::anyhow!("cannot read {}: {err}", path.display()))
+    }
+
+    fn download(&self, day: u8, path: &Path) -> anyhow::Result<String> {
+        let Some(session) = &self.session else {
+            anyhow::bail!(
+                "no input at {} and {SESSION_VAR} is not set to download it",
+                path.display()
+            );
+        };
+
+        self.wait_for_turn();
+        let url = format!("{}/day/{day}/input", self.base_url.trim_end_matches('/'));
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .user_agent(&self.user_agent)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .http_status_as_error(false)
+            .build()
+            .into();
+        let mut response = agent
+            .get(&url)
+            .header("Cookie", format!("session={session}"))
+            .call()
+            .map_err(|err| anyhow::anyhow!("cannot download {url}: {err}"))?;
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .map_err(|err| anyhow::anyhow!("cannot download {url}: {err}"))?;
+
+        match response.status().as_u16() {
+            200 => Ok(body),
+            404 => anyhow::bail!("day {day} is not available at {url} yet"),
+            400 | 401 | 403 | 500 => anyhow::bail!(
+                "cannot download {url}: {}, is {SESSION_VAR} still valid?",
+                response.status()
+            ),
+            _ => anyhow::bail!("cannot download {url}: {}", response.status()),
+        }
+    }
+
+    /// Sleeps until `min_interval` has passed since the previous download.
+    fn wait_for_turn(&self) {
+        let mut last = self.last_request.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(last) = *last {
+            std::thread::sleep(self.min_interval.saturating_sub(last.elapsed()));
+        }
+        *last = Some(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Inputs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    /// Serves `responses` as `(status, body)` one connection each and sends
+    /// the head of every request it got.
+    fn stub_server(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/2022", listener.local_addr().unwrap());
+        let (requests, received) = mpsc::channel();
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut head = String::new();
+                let mut reader = BufReader::new(&stream);
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap() <= 2 {
+                        break;
+                    }
+                    head += &line;
+                }
+                requests.send(head).unwrap();
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+        (url, received)
+    }
+
+    fn inputs(name: &str, url: String) -> Inputs {
+        let dir = std::env::temp_dir().join(format!("aoc-2022-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut inputs = Inputs::from_env(dir);
+        inputs.base_url = url;
+        inputs.session = Some("53551on".into());
+        inputs.user_agent = "aoc-2022 tests".into();
+        inputs
+    }
+
+    #[test]
+    fn test_download_once() -> anyhow::Result<()> {
+        let (url, requests) = stub_server(vec![(200, "1000\n2000\n")]);
+        let inputs = inputs("download", url);
+
+        assert_eq!(inputs.get(1)?, "1000\n2000\n");
+        let request = requests.recv()?;
+        assert!(request.starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        let headers = request.to_lowercase();
+        assert!(
+            headers.contains("\r\ncookie: session=53551on\r\n"),
+            "{request}"
+        );
+        assert!(
+            headers.contains("\r\nuser-agent: aoc-2022 tests\r\n"),
+            "{request}"
+        );
+
+        // the stub only answers once, this has to come from the cache
+        assert_eq!(inputs.get(1)?, "1000\n2000\n");
+        assert!(inputs.path(1).ends_with("day01.txt"));
+        std::fs::remove_dir_all(&inputs.dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_errors() -> anyhow::Result<()> {
+        let (url, requests) = stub_server(vec![(404, "Not Found"), (400, "log in")]);
+        let mut inputs = inputs("errors", url);
+        inputs.min_interval = Duration::from_millis(200);
+
+        let start = Instant::now();
+        let err = inputs.get(25).unwrap_err().to_string();
+        assert!(err.starts_with("day 25 is not available"), "{err}");
+        let err = inputs.get(24).unwrap_err().to_string();
+        assert!(err.ends_with("is AOC_SESSION still valid?"), "{err}");
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(requests.iter().count(), 2);
+        assert!(!inputs.path(25).exists() && !inputs.path(24).exists());
+
+        inputs.session = None;
+        let err = inputs.get(3).unwrap_err().to_string();
+        assert!(
+            err.ends_with("AOC_SESSION is not set to download it"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use std::ops::RangeInclusive;
+
+/// A set of integers stored as sorted, disjoint ranges.
+///
+/// Ranges that overlap or touch are merged as they are inserted, so
+/// `1..=3` and `4..=6` become `1..=6`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<RangeInclusive<i64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<i64>) {
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = range.into_inner();
+        // everything from `first` to before `last` overlaps or touches the
+        // new range and is merged into it
+        let first = self
+            .ranges
+            .partition_point(|r| *r.end() < start.saturating_sub(1));
+        let last = self
+            .ranges
+            .partition_point(|r| *r.start() <= end.saturating_add(1));
+        if first < last {
+            start = start.min(*self.ranges[first].start());
+            end = end.max(*self.ranges[last - 1].end());
+        }
+        self.ranges.splice(first..last, [start..=end]);
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        let i = self.ranges.partition_point(|r| *r.end() < n);
+        self.ranges.get(i).is_some_and(|r| r.contains(&n))
+    }
+
+    /// Number of integers in the set.
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|r| r.end().abs_diff(*r.start()) + 1)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The ranges in ascending order.
+    pub fn ranges(&self) -> &[RangeInclusive<i64>] {
+        &self.ranges
+    }
+
+    /// The integers in `within` that are not in the set.
+    pub fn gaps(&self, within: RangeInclusive<i64>) -> IntervalSet {
+        let (start, end) = within.into_inner();
+        let mut gaps = IntervalSet::new();
+        let mut next = start;
+        for range in &self.ranges {
+            if next > end {
+                break;
+            }
+            if *range.start() > next {
+                gaps.insert(next..=(*range.start() - 1).min(end));
+            }
+            next = next.max(range.end().saturating_add(1));
+        }
+        gaps.insert(next..=end);
+        gaps
+    }
+}
+
+impl FromIterator<RangeInclusive<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<i64>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalSet;
+
+    #[test]
+    fn test_insert() {
+        let mut set = IntervalSet::from_iter([10..=12, 1..=3]);
+        assert_eq!(set.ranges(), [1..=3, 10..=12]);
+        set.insert(4..=6);
+        assert_eq!(set.ranges(), [1..=6, 10..=12]);
+        set.insert(8..=8);
+        set.insert(5..=9);
+        assert_eq!(set.ranges(), [1..=12]);
+        set.insert(-5..=-4);
+        assert_eq!(set.ranges(), [-5..=-4, 1..=12]);
+        assert_eq!(set.len(), 14);
+        assert!(set.contains(-4) && set.contains(12));
+        assert!(!set.contains(0) && !set.contains(13));
+        assert!(IntervalSet::new().is_empty());
+    }
+
+    #[test]
+    fn test_gaps() {
+        let set = IntervalSet::from_iter([2..=3, 6..=8, 12..=20]);
+        assert_eq!(set.gaps(0..=14).ranges(), [0..=1, 4..=5, 9..=11]);
+        assert_eq!(set.gaps(3..=7).ranges(), [4..=5]);
+        assert_eq!(set.gaps(21..=30).ranges(), [21..=30]);
+        assert!(set.gaps(12..=20).is_empty());
+    }
+}
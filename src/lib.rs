@@ -1,17 +1,79 @@
+//! Advent of Code 2022 solutions, usable as a library.
+//!
+//! Every `dayNN` module exposes its parser as `input_gen`, its solvers as
+//! `solve_part1`/`solve_part2` and a `DayNN` type implementing
+//! [`solution::Solution`]. Days with reusable building blocks make them
+//! public too, e.g. [`day13::PacketPart::compare`], [`day10::Cpu`] and
+//! [`day07::Directory`]. Structures shared by several days live in
+//! [`grid`], [`interval`] and [`voxel`].
+//!
+//! The modules behind the command line tool, such as the input generators,
+//! the runner and the reports, are public for the binary, benches and fuzz
+//! targets only. They are hidden from the documentation and may change in
+//! any release.
+//!
+//! Plain values expose their fields: coordinates like [`grid::Pos`],
+//! [`voxel::Voxel`] and [`voxel::Bounds`], the location in a [`ParseError`]
+//! and the number of a [`solution::Day`]. Structures that keep an invariant,
+//! like [`grid::Grid`], [`interval::IntervalSet`], [`voxel::VoxelSet`] and
+//! the parsed inputs of the days, keep their fields private so the
+//! representation can change without breaking dependents;
+//! [`solution::Answer`] is `#[non_exhaustive]` for the same reason.
+
 use aoc_runner_derive::aoc_lib;
 
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+mod error;
+#[cfg(test)]
+mod fixtures;
+pub mod grid;
+mod input;
+pub mod interval;
+#[cfg(test)]
+mod reference;
+pub mod solution;
+pub mod voxel;
+
+// Tooling behind the `aoc-2022` binary, the benches and the fuzz targets,
+// not part of the API.
+#[doc(hidden)]
+pub mod gen;
+#[doc(hidden)]
+pub mod inputs;
+#[doc(hidden)]
+pub mod memory;
+#[doc(hidden)]
+pub mod report;
+#[doc(hidden)]
+pub mod runner;
+#[doc(hidden)]
+pub mod scaffold;
+#[doc(hidden)]
+pub mod stream;
+#[doc(hidden)]
+pub mod trace;
+#[doc(hidden)]
+pub mod verify;
+#[doc(hidden)]
+pub mod viz;
+
+pub use error::ParseError;
 
 aoc_lib! { year = 2022 }
@@ -0,0 +1,149 @@
+//! Allocation accounting for solvers.
+//!
+//! With the `count-alloc` feature the crate installs a global allocator that
+//! counts, per thread, every allocation made through it. Without the feature
+//! [`measure`] just runs the closure and reports nothing.
+
+use serde::{Deserialize, Serialize};
+
+/// Heap usage of a measured piece of code.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    /// Number of allocations, a reallocation counts as one.
+    pub allocations: u64,
+    /// Total bytes requested, including those freed again.
+    pub bytes: u64,
+    /// Highest number of live bytes above what was live before.
+    pub peak_bytes: u64,
+}
+
+impl std::fmt::Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} bytes, peak {} bytes",
+            self.allocations, self.bytes, self.peak_bytes
+        )
+    }
+}
+
+#[cfg(feature = "count-alloc")]
+mod counting {
+    use super::Stats;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    struct Counters {
+        allocations: Cell<u64>,
+        bytes: Cell<u64>,
+        // memory freed on another thread than it was allocated on makes these
+        // drift, which is why they are signed
+        live: Cell<i64>,
+        peak: Cell<i64>,
+    }
+
+    thread_local! {
+        static COUNTERS: Counters = const {
+            Counters {
+                allocations: Cell::new(0),
+                bytes: Cell::new(0),
+                live: Cell::new(0),
+                peak: Cell::new(0),
+            }
+        };
+    }
+
+    fn record(allocated: usize, freed: usize) {
+        let _ = COUNTERS.try_with(|c| {
+            if allocated > 0 {
+                c.allocations.set(c.allocations.get() + 1);
+                c.bytes.set(c.bytes.get() + allocated as u64);
+            }
+            let live = c.live.get() + allocated as i64 - freed as i64;
+            c.live.set(live);
+            c.peak.set(c.peak.get().max(live));
+        });
+    }
+
+    /// Counts allocations and hands them on to the system allocator.
+    pub struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            record(layout.size(), 0);
+            System.alloc(layout)
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            record(layout.size(), 0);
+            System.alloc_zeroed(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            record(0, layout.size());
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            record(new_size, layout.size());
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Stats>) {
+        let start = COUNTERS.with(|c| {
+            c.peak.set(c.live.get());
+            (c.allocations.get(), c.bytes.get(), c.live.get())
+        });
+        let result = f();
+        let stats = COUNTERS.with(|c| Stats {
+            allocations: c.allocations.get() - start.0,
+            bytes: c.bytes.get() - start.1,
+            peak_bytes: (c.peak.get() - start.2).max(0) as u64,
+        });
+        (result, Some(stats))
+    }
+}
+
+/// Runs `f` and reports the allocations it made on the current thread,
+/// `None` without the `count-alloc` feature.
+#[cfg(feature = "count-alloc")]
+pub use counting::measure;
+
+/// Runs `f` and reports the allocations it made on the current thread,
+/// `None` without the `count-alloc` feature.
+#[cfg(not(feature = "count-alloc"))]
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Stats>) {
+    (f(), None)
+}
+
+#[cfg(all(test, feature = "count-alloc"))]
+mod tests {
+    use super::{measure, Stats};
+
+    #[test]
+    fn test_measure() {
+        let (len, stats) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(8);
+            v.extend(0..8);
+            let boxed = Box::new([0u8; 100]);
+            drop(boxed);
+            v.len()
+        });
+        assert_eq!(len, 8);
+        assert_eq!(
+            stats,
+            Some(Stats {
+                allocations: 2,
+                bytes: 164,
+                peak_bytes: 164,
+            })
+        );
+
+        let (_, stats) = measure(|| 1 + 1);
+        assert_eq!(stats.unwrap().allocations, 0);
+    }
+}
@@ -0,0 +1,289 @@
+//! Deliberately naive solutions, used as the oracle for the property tests
+//! of the optimized solvers.
+//!
+//! Everything here works directly on the puzzle text and trades speed for
+//! being obviously correct, so it shares no code with the real solutions.
+
+use std::collections::{HashSet, VecDeque};
+
+/// Position right after the first `n` distinct characters, if there is one.
+pub fn day06(input: &str, n: usize) -> Option<usize> {
+    let chars: Vec<char> = input.chars().collect();
+    (n..=chars.len()).find(|&end| chars[end - n..end].iter().collect::<HashSet<_>>().len() == n)
+}
+
+fn day04_sections(input: &str) -> Vec<(HashSet<u32>, HashSet<u32>)> {
+    let range = |r: &str| {
+        let (a, b) = r.split_once('-').unwrap();
+        (a.parse().unwrap()..=b.parse().unwrap()).collect()
+    };
+    input
+        .lines()
+        .map(|line| {
+            let (r1, r2) = line.split_once(',').unwrap();
+            (range(r1), range(r2))
+        })
+        .collect()
+}
+
+/// Number of pairs where one elf's sections contain the other's.
+pub fn day04_part1(input: &str) -> usize {
+    day04_sections(input)
+        .iter()
+        .filter(|(a, b)| a.is_subset(b) || b.is_subset(a))
+        .count()
+}
+
+/// Number of pairs that share at least one section.
+pub fn day04_part2(input: &str) -> usize {
+    day04_sections(input)
+        .iter()
+        .filter(|(a, b)| !a.is_disjoint(b))
+        .count()
+}
+
+/// The trees seen from `(y, x)` walking in each of the four directions,
+/// nearest first.
+fn day08_lines(grid: &[Vec<u8>], y: usize, x: usize) -> [Vec<u8>; 4] {
+    let column = |ys: &mut dyn Iterator<Item = usize>| ys.map(|y| grid[y][x]).collect();
+    [
+        column(&mut (0..y).rev()),
+        column(&mut (y + 1..grid.len())),
+        grid[y][..x].iter().rev().copied().collect(),
+        grid[y][x + 1..].to_vec(),
+    ]
+}
+
+fn day08_grid(input: &str) -> Vec<Vec<u8>> {
+    input.lines().map(|line| line.bytes().collect()).collect()
+}
+
+/// Number of trees that are visible from outside the grid.
+pub fn day08_part1(input: &str) -> usize {
+    let grid = day08_grid(input);
+    let mut visible = 0;
+    for y in 0..grid.len() {
+        for x in 0..grid[y].len() {
+            let height = grid[y][x];
+            if day08_lines(&grid, y, x)
+                .iter()
+                .any(|line| line.iter().all(|&tree| tree < height))
+            {
+                visible += 1;
+            }
+        }
+    }
+    visible
+}
+
+/// Highest scenic score of any tree.
+pub fn day08_part2(input: &str) -> usize {
+    let grid = day08_grid(input);
+    let mut best = 0;
+    for y in 0..grid.len() {
+        for x in 0..grid[y].len() {
+            let height = grid[y][x];
+            let score = day08_lines(&grid, y, x)
+                .iter()
+                .map(|line| {
+                    let mut distance = 0;
+                    for &tree in line {
+                        distance += 1;
+                        if tree >= height {
+                            break;
+                        }
+                    }
+                    distance
+                })
+                .product();
+            best = best.max(score);
+        }
+    }
+    best
+}
+
+struct Monkey {
+    items: VecDeque<u128>,
+    operation: Vec<String>,
+    divisor: u128,
+    if_true: usize,
+    if_false: usize,
+}
+
+fn day11_monkeys(input: &str) -> Vec<Monkey> {
+    input
+        .trim_end()
+        .split("\n\n")
+        .map(|block| {
+            let lines: Vec<&str> = block.lines().map(str::trim).collect();
+            let after = |line: &str, prefix: &str| line.strip_prefix(prefix).unwrap().to_owned();
+            let last_number = |line: &str| line.rsplit(' ').next().unwrap().parse().unwrap();
+            Monkey {
+                items: after(lines[1], "Starting items:")
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| item.parse().unwrap())
+                    .collect(),
+                operation: after(lines[2], "Operation: new = ")
+                    .split(' ')
+                    .map(str::to_owned)
+                    .collect(),
+                divisor: last_number(lines[3]),
+                if_true: last_number(lines[4]) as usize,
+                if_false: last_number(lines[5]) as usize,
+            }
+        })
+        .collect()
+}
+
+/// Product of the two highest inspection counts after `rounds` rounds.
+///
+/// Without `relief` the worry levels are kept modulo the product of all
+/// divisors, which keeps every divisibility test intact.
+pub fn day11(input: &str, rounds: usize, relief: bool) -> usize {
+    let mut monkeys = day11_monkeys(input);
+    let product: u128 = monkeys.iter().map(|m| m.divisor).product();
+    let mut inspected = vec![0; monkeys.len()];
+
+    for _ in 0..rounds {
+        for i in 0..monkeys.len() {
+            while let Some(old) = monkeys[i].items.pop_front() {
+                inspected[i] += 1;
+                let value = |operand: &str| match operand {
+                    "old" => old,
+                    n => n.parse().unwrap(),
+                };
+                let op = &monkeys[i].operation;
+                let mut new = match op[1].as_str() {
+                    "+" => value(&op[0]) + value(&op[2]),
+                    "*" => value(&op[0]) * value(&op[2]),
+                    _ => unreachable!(),
+                };
+                if relief {
+                    new /= 3;
+                } else {
+                    new %= product;
+                }
+                let target = if new % monkeys[i].divisor == 0 {
+                    monkeys[i].if_true
+                } else {
+                    monkeys[i].if_false
+                };
+                monkeys[target].items.push_back(new);
+            }
+        }
+    }
+
+    inspected.sort_unstable();
+    inspected.iter().rev().take(2).product()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{day04, day06, day08, day11};
+    use proptest::prelude::*;
+    use std::fmt::Write;
+
+    fn section_range() -> impl Strategy<Value = String> {
+        (1u32..100, 0u32..20).prop_map(|(start, len)| format!("{start}-{}", start + len))
+    }
+
+    fn day04_input() -> impl Strategy<Value = String> {
+        prop::collection::vec((section_range(), section_range()), 1..50).prop_map(|pairs| {
+            pairs
+                .into_iter()
+                .map(|(a, b)| format!("{a},{b}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
+
+    fn day08_input() -> impl Strategy<Value = String> {
+        (1usize..12, 1usize..12)
+            .prop_flat_map(|(width, height)| {
+                prop::collection::vec(prop::collection::vec(b'0'..=b'9', width), height)
+            })
+            .prop_map(|rows| {
+                rows.into_iter()
+                    .map(|row| String::from_utf8(row).unwrap())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+    }
+
+    /// Monkeys other than the first only throw to lower indices and nobody
+    /// squares, so the part 1 worry levels can't overflow.
+    fn day11_input() -> impl Strategy<Value = String> {
+        (2usize..8)
+            .prop_flat_map(|n| {
+                let monkeys = (0..n)
+                    .map(|i| {
+                        let targets = if i == 0 { 1..n } else { 0..i };
+                        (
+                            prop::collection::vec(1u64..100, 0..6),
+                            prop_oneof![
+                                (1u64..10).prop_map(|f| format!("old + {f}")),
+                                (2u64..8).prop_map(|f| format!("old * {f}")),
+                                Just("old + old".to_owned()),
+                            ],
+                            prop::sample::select(vec![2u64, 3, 5, 7, 11, 13, 17, 19, 23]),
+                            targets.clone(),
+                            targets,
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                monkeys
+            })
+            .prop_map(|monkeys| {
+                let mut input = String::new();
+                for (i, (items, op, divisor, if_true, if_false)) in monkeys.into_iter().enumerate()
+                {
+                    let items: Vec<_> = items.iter().map(u64::to_string).collect();
+                    writeln!(input, "Monkey {i}:").unwrap();
+                    writeln!(input, "  Starting items: {}", items.join(", ")).unwrap();
+                    writeln!(input, "  Operation: new = {op}").unwrap();
+                    writeln!(input, "  Test: divisible by {divisor}").unwrap();
+                    writeln!(input, "    If true: throw to monkey {if_true}").unwrap();
+                    writeln!(input, "    If false: throw to monkey {if_false}\n").unwrap();
+                }
+                input
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_day06(noise in "[a-f]{1,60}", tail in "[a-z]{0,20}") {
+            // a short alphabet makes inputs without markers likely
+            let input = format!("{noise}{tail}");
+            let parsed = day06::input_gen(&input).unwrap();
+            prop_assert_eq!(day06::solve_part1(&parsed), super::day06(&input, 4));
+            prop_assert_eq!(day06::solve_part2(&parsed), super::day06(&input, 14));
+        }
+
+        #[test]
+        fn test_day04(input in day04_input()) {
+            let parsed = day04::input_gen(&input).unwrap();
+            prop_assert_eq!(day04::solve_part1(&parsed), super::day04_part1(&input));
+            prop_assert_eq!(day04::solve_part2(&parsed), super::day04_part2(&input));
+        }
+
+        #[test]
+        fn test_day08(input in day08_input()) {
+            let parsed = day08::input_gen(&input).unwrap();
+            prop_assert_eq!(day08::solve_part1(&parsed), super::day08_part1(&input));
+            prop_assert_eq!(day08::solve_part2(&parsed), Some(super::day08_part2(&input)));
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_day11(input in day11_input()) {
+            let parsed = day11::input_gen(&input).unwrap();
+            prop_assert_eq!(day11::solve_part1(&parsed), Some(super::day11(&input, 20, true)));
+            prop_assert_eq!(day11::solve_part2(&parsed), super::day11(&input, 10_000, false));
+        }
+    }
+}
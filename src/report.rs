@@ -0,0 +1,167 @@
+use crate::memory::Stats;
+use crate::solution::{Answer, Part};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How an answer has to be read, multi-line text is meant for humans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnswerType {
+    Integer,
+    String,
+    Text,
+}
+
+/// An answer as it appears in JSON, integers stay numbers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AnswerValue {
+    Integer(i64),
+    String(String),
+}
+
+/// The outcome of one part of one day in a machine-readable form.
+///
+/// Exactly one of `answer` and `error` is set, unless the input simply has no
+/// answer for this part, then both are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<AnswerValue>,
+    pub answer_type: Option<AnswerType>,
+    /// Time spent parsing the input, shared by both parts of a day.
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+    /// Allocations while parsing, only with the `count-alloc` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_memory: Option<Stats>,
+    /// Allocations while solving, only with the `count-alloc` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solve_memory: Option<Stats>,
+    pub error: Option<String>,
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+impl Record {
+    pub fn answer(
+        day: u8,
+        part: Part,
+        answer: Option<&Answer>,
+        parse_time: Duration,
+        solve_time: Duration,
+    ) -> Self {
+        let (answer, answer_type) = match answer {
+            Some(Answer::Integer(n)) => (Some(AnswerValue::Integer(*n)), Some(AnswerType::Integer)),
+            Some(Answer::String(s)) => (
+                Some(AnswerValue::String(s.clone())),
+                Some(AnswerType::String),
+            ),
+            Some(Answer::Text(s)) => (Some(AnswerValue::String(s.clone())), Some(AnswerType::Text)),
+            None => (None, None),
+        };
+        Self {
+            day,
+            part: part.into(),
+            answer,
+            answer_type,
+            parse_ns: Some(nanos(parse_time)),
+            solve_ns: Some(nanos(solve_time)),
+            parse_memory: None,
+            solve_memory: None,
+            error: None,
+        }
+    }
+
+    /// A part that could not be solved, e.g. because its input is missing or
+    /// does not parse.
+    pub fn error(day: u8, part: Part, error: impl ToString) -> Self {
+        Self {
+            day,
+            part: part.into(),
+            answer: None,
+            answer_type: None,
+            parse_ns: None,
+            solve_ns: None,
+            parse_memory: None,
+            solve_memory: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+/// The JSON document printed for a run of one or more days.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub results: Vec<Record>,
+    /// Sum of all parse and solve times, as if run one after the other.
+    pub total_ns: u64,
+    /// Time from the start of the run until the last part was solved.
+    pub wall_ns: u64,
+}
+
+impl Report {
+    pub fn new(results: Vec<Record>, wall_time: Duration) -> Self {
+        let mut parsed = vec![];
+        let mut total_ns = 0;
+        for record in &results {
+            // both parts of a day share their parse time, count it once
+            if let Some(parse_ns) = record.parse_ns {
+                if !parsed.contains(&record.day) {
+                    parsed.push(record.day);
+                    total_ns += parse_ns;
+                }
+            }
+            total_ns += record.solve_ns.unwrap_or(0);
+        }
+        Self {
+            results,
+            total_ns,
+            wall_ns: nanos(wall_time),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnswerType, AnswerValue, Record, Report};
+    use crate::solution::{Answer, Part};
+    use std::time::Duration;
+
+    #[test]
+    fn test_json() -> anyhow::Result<()> {
+        let ms = Duration::from_millis;
+        let results = vec![
+            Record::answer(10, Part::One, Some(&Answer::Integer(13140)), ms(1), ms(2)),
+            Record::answer(
+                10,
+                Part::Two,
+                Some(&Answer::Text("#.\n".into())),
+                ms(1),
+                ms(3),
+            ),
+            Record::answer(12, Part::One, None, ms(4), ms(5)),
+            Record::error(13, Part::Two, "day 13, line 1: oops"),
+        ];
+        let report = Report::new(results, ms(7));
+        assert_eq!(report.total_ns, 15_000_000);
+
+        let json = serde_json::to_value(&report)?;
+        assert_eq!(json["results"][0]["answer"], 13140);
+        assert_eq!(json["results"][1]["answer_type"], "text");
+        assert!(json["results"][2]["answer"].is_null());
+        assert_eq!(json["results"][3]["error"], "day 13, line 1: oops");
+
+        let back: Report = serde_json::from_value(json)?;
+        assert_eq!(back, report);
+        assert_eq!(
+            back.results[1].answer,
+            Some(AnswerValue::String("#.\n".into()))
+        );
+        assert_eq!(back.results[1].answer_type, Some(AnswerType::Text));
+        Ok(())
+    }
+}
@@ -0,0 +1,440 @@
+use crate::{
+    memory::{self, Stats},
+    report::{Record, Report},
+    solution::{Answer, Day, Part},
+    ParseError,
+};
+use rayon::prelude::*;
+use std::{
+    any::Any,
+    fmt::{self, Write},
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+/// Answer and solve time of one part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Option<Answer>,
+    pub solve_time: Duration,
+    /// Only measured with the `count-alloc` feature.
+    pub memory: Option<Stats>,
+}
+
+/// Both parts of one day, solved from the same parsed input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayRun {
+    pub day: u8,
+    pub parse_time: Duration,
+    /// Only measured with the `count-alloc` feature.
+    pub parse_memory: Option<Stats>,
+    /// The parts that didn't panic.
+    pub parts: Vec<PartRun>,
+}
+
+impl DayRun {
+    /// Parse time plus the solve time of every part.
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|p| p.solve_time).sum::<Duration>()
+    }
+
+    pub fn part(&self, part: Part) -> Option<&PartRun> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+}
+
+/// Why a day, or one part of it, has no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    Parse(ParseError),
+    /// The parser, if there is no `part`, or the solver of `part` panicked.
+    Panic {
+        day: u8,
+        part: Option<Part>,
+        message: String,
+    },
+}
+
+impl Failure {
+    pub fn day(&self) -> u8 {
+        match self {
+            Failure::Parse(err) => err.day,
+            Failure::Panic { day, .. } => *day,
+        }
+    }
+
+    /// The parts without an answer.
+    pub fn parts(&self) -> Vec<Part> {
+        match self {
+            Failure::Panic {
+                part: Some(part), ..
+            } => vec![*part],
+            _ => Part::ALL.to_vec(),
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Parse(err) => err.fmt(f),
+            Failure::Panic {
+                day,
+                part: Some(part),
+                message,
+            } => write!(f, "day {day:02} part {part} panicked: {message}"),
+            Failure::Panic {
+                day,
+                part: None,
+                message,
+            } => write!(f, "day {day:02} panicked while parsing: {message}"),
+        }
+    }
+}
+
+/// Everything [`run_all`] found out.
+#[derive(Debug)]
+pub struct Summary {
+    pub days: Vec<DayRun>,
+    pub errors: Vec<Failure>,
+    /// Sum of all parse and solve times, as if run one after the other.
+    pub total_time: Duration,
+    /// Time until the last part was solved.
+    pub wall_time: Duration,
+}
+
+impl Summary {
+    /// One record per day and part, failed ones included.
+    pub fn report(&self) -> Report {
+        let mut results: Vec<Record> = self
+            .days
+            .iter()
+            .flat_map(|day| {
+                day.parts.iter().map(|part| {
+                    let mut record = Record::answer(
+                        day.day,
+                        part.part,
+                        part.answer.as_ref(),
+                        day.parse_time,
+                        part.solve_time,
+                    );
+                    record.parse_memory = day.parse_memory;
+                    record.solve_memory = part.memory;
+                    record
+                })
+            })
+            .chain(self.errors.iter().flat_map(|err| {
+                err.parts()
+                    .into_iter()
+                    .map(move |part| Record::error(err.day(), part, err))
+            }))
+            .collect();
+        results.sort_by_key(|r| (r.day, r.part));
+        Report::new(results, self.wall_time)
+    }
+}
+
+/// The message a panic was started with.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or("unknown cause", |message| message)
+            .to_owned(),
+    }
+}
+
+/// Runs a day, a panicking parser or solver doesn't take the other days down
+/// with it.
+fn run_day(day: &Day, input: &str) -> (Option<DayRun>, Vec<Failure>) {
+    let ((parsed, parse_time), parse_memory) = memory::measure(|| {
+        let start = Instant::now();
+        let parsed = panic::catch_unwind(|| day.parse(input));
+        (parsed, start.elapsed())
+    });
+    let parsed = match parsed {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(err)) => return (None, vec![Failure::Parse(err)]),
+        Err(payload) => {
+            let message = panic_message(payload);
+            let failure = Failure::Panic {
+                day: day.day,
+                part: None,
+                message,
+            };
+            return (None, vec![failure]);
+        }
+    };
+
+    let results: Vec<_> = Part::ALL
+        .into_par_iter()
+        .map(|part| {
+            let ((answer, solve_time), memory) = memory::measure(|| {
+                let start = Instant::now();
+                // the parsed input is only read, a panic can't leave it broken
+                let answer = panic::catch_unwind(AssertUnwindSafe(|| day.solve(&parsed, part)));
+                (answer, start.elapsed())
+            });
+            match answer {
+                Ok(answer) => Ok(PartRun {
+                    part,
+                    answer,
+                    solve_time,
+                    memory,
+                }),
+                Err(payload) => Err(Failure::Panic {
+                    day: day.day,
+                    part: Some(part),
+                    message: panic_message(payload),
+                }),
+            }
+        })
+        .collect();
+
+    let mut parts = vec![];
+    let mut failures = vec![];
+    for result in results {
+        match result {
+            Ok(part) => parts.push(part),
+            Err(failure) => failures.push(failure),
+        }
+    }
+    let run = DayRun {
+        day: day.day,
+        parse_time,
+        parse_memory,
+        parts,
+    };
+    (Some(run), failures)
+}
+
+/// Parses and solves every day on the global thread pool.
+///
+/// The parts of a day are solved concurrently once its input is parsed.
+/// Days that fail to parse and parsers or solvers that panic end up in
+/// [`Summary::errors`].
+pub fn run_all(inputs: &[(&Day, String)]) -> Summary {
+    let start = Instant::now();
+    let results: Vec<_> = inputs
+        .par_iter()
+        .map(|(day, input)| run_day(day, input))
+        .collect();
+    let wall_time = start.elapsed();
+
+    let mut days = vec![];
+    let mut errors = vec![];
+    for (day, failures) in results {
+        days.extend(day);
+        errors.extend(failures);
+    }
+    let total_time = days.iter().map(DayRun::total_time).sum();
+
+    Summary {
+        days,
+        errors,
+        total_time,
+        wall_time,
+    }
+}
+
+impl fmt::Display for Summary {
+    /// An aligned table with one row per day, multi-line answers follow below.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = |d: Duration| format!("{d:.2?}");
+        let solve_time = |day: &DayRun, part| {
+            day.part(part)
+                .map_or("-".to_owned(), |p| time(p.solve_time))
+        };
+        let answer = |day: &DayRun, part| match day.part(part).map(|p| &p.answer) {
+            Some(Some(Answer::Text(_))) => "(see below)".to_owned(),
+            Some(Some(answer)) => answer.to_string(),
+            Some(None) => "-".to_owned(),
+            None => "(panicked)".to_owned(),
+        };
+        let total_solve_time = |part| {
+            self.days
+                .iter()
+                .filter_map(|d| d.part(part))
+                .map(|p| p.solve_time)
+                .sum()
+        };
+
+        let mut rows = vec![[
+            "day".to_owned(),
+            "parse".to_owned(),
+            "part 1".to_owned(),
+            "part 2".to_owned(),
+            "total".to_owned(),
+            "answer 1".to_owned(),
+            "answer 2".to_owned(),
+        ]];
+        for day in &self.days {
+            rows.push([
+                format!("{:02}", day.day),
+                time(day.parse_time),
+                solve_time(day, Part::One),
+                solve_time(day, Part::Two),
+                time(day.total_time()),
+                answer(day, Part::One),
+                answer(day, Part::Two),
+            ]);
+        }
+        rows.push([
+            "total".to_owned(),
+            time(self.days.iter().map(|d| d.parse_time).sum()),
+            time(total_solve_time(Part::One)),
+            time(total_solve_time(Part::Two)),
+            time(self.total_time),
+            String::new(),
+            String::new(),
+        ]);
+
+        let mut widths = [0; 7];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let rule: String = widths
+            .iter()
+            .map(|&w| "-".repeat(w))
+            .collect::<Vec<_>>()
+            .join("-+-");
+
+        for (i, row) in rows.iter().enumerate() {
+            if i == 1 || i == rows.len() - 1 {
+                writeln!(f, "{rule}")?;
+            }
+            let mut line = String::new();
+            for (j, (cell, &width)) in row.iter().zip(&widths).enumerate() {
+                if j > 0 {
+                    line.push_str(" | ");
+                }
+                // names and answers are left aligned, times right aligned
+                if j == 0 || j >= 5 {
+                    write!(line, "{cell:<width$}")?;
+                } else {
+                    write!(line, "{cell:>width$}")?;
+                }
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        writeln!(f, "wall time {}", time(self.wall_time))?;
+
+        if self.days.iter().any(|d| d.parse_memory.is_some()) {
+            writeln!(f)?;
+            for day in &self.days {
+                let steps = std::iter::once(("parse".to_owned(), day.parse_memory)).chain(
+                    day.parts
+                        .iter()
+                        .map(|p| (format!("part {}", p.part), p.memory)),
+                );
+                for (step, stats) in steps {
+                    if let Some(stats) = stats {
+                        writeln!(f, "day {:02} {step:<6}: {stats}", day.day)?;
+                    }
+                }
+            }
+        }
+
+        for day in &self.days {
+            for part in &day.parts {
+                if let Some(Answer::Text(text)) = &part.answer {
+                    write!(f, "\nday {:02} part {}:\n{text}", day.day, part.part)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_all, Failure};
+    use crate::report::AnswerValue;
+    use crate::solution::{self, Answer, Day, Part, Solution};
+    use crate::ParseError;
+
+    /// Panics solving part 1.
+    struct Panicky;
+
+    impl Solution for Panicky {
+        const DAY: u8 = 25;
+        type Input = ();
+
+        fn parse(_input: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &Self::Input) -> Option<Answer> {
+            panic!("no part 1")
+        }
+
+        fn part2(_input: &Self::Input) -> Option<Answer> {
+            Some(2u32.into())
+        }
+    }
+
+    #[test]
+    fn test_run_all() -> anyhow::Result<()> {
+        let inputs = [
+            (solution::day(1).unwrap(), "1\n2\n\n4".to_owned()),
+            (solution::day(4).unwrap(), "2-4,3-3\n1-1,2-2".to_owned()),
+            (solution::day(8).unwrap(), "12\n3".to_owned()),
+        ];
+        let summary = run_all(&inputs);
+
+        assert_eq!(summary.days.len(), 2);
+        assert_eq!(summary.errors.len(), 1);
+        let answers: Vec<_> = summary
+            .days
+            .iter()
+            .flat_map(|d| d.parts.iter().map(|p| p.answer.clone()))
+            .collect();
+        assert_eq!(
+            answers,
+            [4, 7, 1, 1].map(|n| Some(Answer::Integer(n))).to_vec()
+        );
+
+        let table = summary.to_string();
+        assert!(table.lines().any(|l| l.starts_with("04    |")));
+        let report = summary.report();
+        assert_eq!(report.results.len(), 6);
+        assert_eq!(report.results[3].answer, Some(AnswerValue::Integer(1)));
+        assert!(report.results[5].error.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_panic() {
+        let panicky = Day::new::<Panicky>();
+        let inputs = [
+            (solution::day(1).unwrap(), "1\n2\n\n4".to_owned()),
+            (&panicky, String::new()),
+        ];
+        let summary = run_all(&inputs);
+
+        assert_eq!(summary.days.len(), 2);
+        assert!(summary.days[1].part(Part::One).is_none());
+        assert_eq!(
+            summary.errors,
+            [Failure::Panic {
+                day: 25,
+                part: Some(Part::One),
+                message: "no part 1".to_owned()
+            }]
+        );
+        assert_eq!(
+            summary.errors[0].to_string(),
+            "day 25 part 1 panicked: no part 1"
+        );
+        assert!(summary.to_string().contains("(panicked)"));
+
+        let report = summary.report();
+        assert_eq!(report.results.len(), 4);
+        assert!(report.results[2].error.is_some());
+        assert_eq!(report.results[3].answer, Some(AnswerValue::Integer(2)));
+    }
+}
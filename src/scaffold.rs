@@ -0,0 +1,333 @@
+//! Scaffolding for a new day, behind the `new-day` command.
+//!
+//! A new day gets its module from `templates/day.rs`, is registered in
+//! `lib.rs`, in [`solution::DAYS`](crate::solution::DAYS), with a placeholder
+//! in [`gen::GENERATORS`](crate::gen::GENERATORS) and as a fuzz target, and
+//! gets a fixture directory waiting for the puzzle example.
+
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+const EXPECTED: &str = "\
+# answers to the examples in this directory, one table per example file
+
+[example_1]
+# part1 = 0
+# part2 = 0
+";
+
+/// The `dayNN.rs` module of `day`.
+fn module(day: u8) -> String {
+    TEMPLATE
+        .replace("{{DAY}}", &day.to_string())
+        .replace("{{NN}}", &format!("{day:02}"))
+}
+
+/// The number of a line like `pub mod day07;`, with `prefix` and `suffix`
+/// around the `day07`.
+fn day_of(line: &str, prefix: &str, suffix: &str) -> Option<u8> {
+    line.strip_prefix(prefix)?
+        .strip_suffix(suffix)?
+        .strip_prefix("day")?
+        .get(..2)?
+        .parse()
+        .ok()
+}
+
+/// Inserts `new` among the consecutive lines `day_of` recognizes, keeping
+/// them ordered by day.
+fn insert_line(
+    lines: &mut Vec<String>,
+    day: u8,
+    new: String,
+    day_of: impl Fn(&str) -> Option<u8>,
+) -> Option<()> {
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| day_of(line).map(|d| (i, d)))
+        .collect::<Vec<_>>();
+    let &(last, _) = days.last()?;
+    let at = days
+        .iter()
+        .find(|&&(_, d)| d > day)
+        .map_or(last + 1, |&(i, _)| i);
+    lines.insert(at, new);
+    Some(())
+}
+
+/// Runs `edit` on the lines of `file`, keeping its line endings.
+fn edit_lines(file: &str, edit: impl FnOnce(&mut Vec<String>) -> Option<()>) -> Option<String> {
+    let crlf = file.contains("\r\n");
+    let mut lines = file.lines().map(str::to_owned).collect::<Vec<_>>();
+    edit(&mut lines)?;
+    let newline = if crlf { "\r\n" } else { "\n" };
+    Some(lines.join(newline) + newline)
+}
+
+/// Adds `pub mod dayNN;` to `lib.rs`.
+fn register_module(lib_rs: &str, day: u8) -> anyhow::Result<String> {
+    edit_lines(lib_rs, |lines| {
+        insert_line(lines, day, format!("pub mod day{day:02};"), |line| {
+            day_of(line, "pub mod ", ";")
+        })
+    })
+    .ok_or_else(|| anyhow::anyhow!("cannot find the `pub mod dayNN;` lines in lib.rs"))
+}
+
+/// Fills lines with the comma separated `items`, indented by four spaces,
+/// the way rustfmt formats short items.
+fn fill(items: &[String]) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    for item in items {
+        if !line.is_empty() && line.len() + item.len() + 2 > 100 {
+            lines.push(std::mem::take(&mut line));
+        }
+        line += if line.is_empty() { "    " } else { " " };
+        line += item;
+        line.push(',');
+    }
+    lines.push(line);
+    lines
+}
+
+/// Formats a `use crate::{...};` the way rustfmt does.
+fn use_crate(items: &[String]) -> Vec<String> {
+    let single = format!("use crate::{{{}}};", items.join(", "));
+    if single.len() <= 100 {
+        return vec![single];
+    }
+    let mut lines = vec!["use crate::{".to_owned()];
+    lines.extend(fill(items));
+    lines.push("};".to_owned());
+    lines
+}
+
+/// Bumps the length of the array `header` starts, which ends in `[`, and
+/// returns its line.
+fn grow_array(lines: &mut [String], header: &str) -> Option<usize> {
+    let at = lines.iter().position(|l| l.starts_with(header))?;
+    let count = lines[at]
+        .strip_prefix(header)?
+        .split_once(']')?
+        .0
+        .parse::<usize>()
+        .ok()?;
+    let rest = lines[at].split_once(']')?.1.to_owned();
+    lines[at] = format!("{header}{}]{rest}", count + 1);
+    Some(at)
+}
+
+/// Adds `dayNN` to the imports and [`DAYS`](crate::solution::DAYS) of
+/// `solution.rs`.
+fn register_solution(solution_rs: &str, day: u8) -> anyhow::Result<String> {
+    edit_lines(solution_rs, |lines| {
+        let start = lines.iter().position(|l| l.starts_with("use crate::{"))?;
+        let len = lines[start..].iter().position(|l| l.ends_with("};"))? + 1;
+        let imports = lines
+            .drain(start..start + len)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut items = imports
+            .trim_start_matches("use crate::{")
+            .trim_end_matches("};")
+            .split(',')
+            .map(|item| item.trim().to_owned())
+            .filter(|item| !item.is_empty())
+            .collect::<Vec<_>>();
+        items.push(format!("day{day:02}"));
+        // modules before types, like rustfmt orders them
+        items.sort_by_key(|item| (item.starts_with(char::is_uppercase), item.clone()));
+        lines.splice(start..start, use_crate(&items));
+
+        grow_array(lines, "pub static DAYS: [Day; ")?;
+        let entry = format!("    Day::new::<day{day:02}::Day{day:02}>(),");
+        insert_line(lines, day, entry, |line| {
+            day_of(line.split("::").nth(2)?, "<", "")
+        })
+    })
+    .ok_or_else(|| anyhow::anyhow!("cannot find the imports and `DAYS` in solution.rs"))
+}
+
+/// Adds a placeholder generator for `day` to `gen.rs`.
+fn register_generator(gen_rs: &str, day: u8) -> anyhow::Result<String> {
+    edit_lines(gen_rs, |lines| {
+        grow_array(lines, "pub static GENERATORS: [Generator; ")?;
+        // the placeholder ignores the size, the default of `gen` will do
+        let entry = format!("    Generator::new(day{day:02}, 100),");
+        insert_line(lines, day, entry, |line| {
+            day_of(line.split(',').next()?, "    Generator::new(", "")
+        })?;
+
+        let tests = lines.iter().position(|l| l == "#[cfg(test)]")?;
+        let generator = format!(
+            "/// Placeholder until day {day} gets a real generator.\n\
+             pub fn day{day:02}(_rng: &mut Rng, _size: usize) -> Generated {{\n    \
+             Generated::new(String::new())\n}}\n\n"
+        );
+        lines.splice(tests..tests, generator.lines().map(str::to_owned));
+        Some(())
+    })
+    .ok_or_else(|| anyhow::anyhow!("cannot find `GENERATORS` and the tests in gen.rs"))
+}
+
+/// The fuzz target parsing arbitrary input for `day`.
+fn fuzz_target(day: u8) -> String {
+    format!(
+        "#![no_main]\n\nuse libfuzzer_sys::fuzz_target;\n\nfuzz_target!(|input: &str| {{\n    \
+         let day = aoc_2022::solution::day({day}).unwrap();\n    let _ = day.parse(input);\n}});\n"
+    )
+}
+
+/// Creates everything a new `day` needs below the crate `root` and returns
+/// the files it wrote.
+///
+/// Nothing is written if the day exists already or one of the registries
+/// does not look as expected.
+pub fn new_day(root: &Path, day: u8) -> anyhow::Result<Vec<PathBuf>> {
+    if !(1..=25).contains(&day) {
+        anyhow::bail!("there is no day {day} in Advent of Code");
+    }
+    let read = |path: &Path| {
+        std::fs::read_to_string(path)
+            .map_err(|err| anyhow::anyhow!("cannot read {}: {err}", path.display()))
+    };
+
+    let module_path = root.join(format!("src/day{day:02}.rs"));
+    if module_path.exists() {
+        anyhow::bail!("{} exists already", module_path.display());
+    }
+    let lib_path = root.join("src/lib.rs");
+    let solution_path = root.join("src/solution.rs");
+    let gen_path = root.join("src/gen.rs");
+    let fixtures = root.join(format!("fixtures/day{day:02}"));
+    let mut files = vec![
+        (module_path, module(day)),
+        (lib_path.clone(), register_module(&read(&lib_path)?, day)?),
+        (
+            solution_path.clone(),
+            register_solution(&read(&solution_path)?, day)?,
+        ),
+        (
+            gen_path.clone(),
+            register_generator(&read(&gen_path)?, day)?,
+        ),
+    ];
+    if !fixtures.exists() {
+        files.push((fixtures.join("example_1.txt"), String::new()));
+        files.push((fixtures.join("expected.toml"), EXPECTED.to_owned()));
+    }
+
+    let fuzz_path = root.join("fuzz/Cargo.toml");
+    if fuzz_path.exists() {
+        let name = format!("day{day:02}");
+        let manifest = read(&fuzz_path)?;
+        if !manifest.contains(&format!("name = \"{name}\"")) {
+            let newline = if manifest.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            };
+            let bin = format!(
+                "\n[[bin]]\nname = \"{name}\"\npath = \"fuzz_targets/{name}.rs\"\ntest = false\n\
+                 doc = false\nbench = false\n"
+            );
+            files.push((fuzz_path, manifest + &bin.replace('\n', newline)));
+        }
+        let target = root.join(format!("fuzz/fuzz_targets/{name}.rs"));
+        if !target.exists() {
+            files.push((target, fuzz_target(day)));
+        }
+    }
+
+    for (path, content) in &files {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|err| anyhow::anyhow!("cannot create {}: {err}", dir.display()))?;
+        }
+        std::fs::write(path, content)
+            .map_err(|err| anyhow::anyhow!("cannot write {}: {err}", path.display()))?;
+    }
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fuzz_target, module, register_generator, register_module, register_solution};
+
+    const LIB_RS: &str =
+        "use aoc_runner_derive::aoc_lib;\n\npub mod day01;\npub mod day03;\nmod error;\n";
+
+    const SOLUTION_RS: &str = "\
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    ParseError,
+};
+
+pub static DAYS: [Day; 13] = [
+    Day::new::<day01::Day01>(),
+    Day::new::<day13::Day13>(),
+];
+";
+
+    const GEN_RS: &str = "\
+pub static GENERATORS: [Generator; 2] = [
+    Generator::new(day01, 10_000),
+    Generator::new(day02, 30_000),
+];
+
+#[cfg(test)]
+mod tests {}
+";
+
+    #[test]
+    fn test_register() -> anyhow::Result<()> {
+        assert_eq!(
+            register_module(LIB_RS, 2)?,
+            LIB_RS.replace("day01;\n", "day01;\npub mod day02;\n")
+        );
+        assert_eq!(
+            register_module(&LIB_RS.replace('\n', "\r\n"), 4)?,
+            LIB_RS
+                .replace("day03;\n", "day03;\npub mod day04;\n")
+                .replace('\n', "\r\n")
+        );
+        assert!(register_module("mod error;\n", 14).is_err());
+
+        let solution_rs = register_solution(SOLUTION_RS, 14)?;
+        assert!(solution_rs.starts_with(
+            "use crate::{\n    day01, day02, day03, day04, day05, day06, day07, day08, day09, \
+             day10, day11, day12, day13,\n    day14, ParseError,\n};\n"
+        ));
+        assert!(solution_rs.ends_with(
+            "pub static DAYS: [Day; 14] = [\n    Day::new::<day01::Day01>(),\n    \
+             Day::new::<day13::Day13>(),\n    Day::new::<day14::Day14>(),\n];\n"
+        ));
+
+        let gen_rs = register_generator(GEN_RS, 3)?;
+        assert!(gen_rs.starts_with(
+            "pub static GENERATORS: [Generator; 3] = [\n    Generator::new(day01, 10_000),\n    \
+             Generator::new(day02, 30_000),\n    Generator::new(day03, 100),\n];\n"
+        ));
+        assert!(gen_rs.contains(
+            "pub fn day03(_rng: &mut Rng, _size: usize) -> Generated {\n    \
+             Generated::new(String::new())\n}\n\n#[cfg(test)]\n"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_templates() {
+        let day = module(7);
+        assert!(day.contains("#[aoc_generator(day7)]"));
+        assert!(day.contains("impl Solution for Day07 {\n    const DAY: u8 = 7;"));
+        assert!(day.contains("\"../fixtures/day07/example_1.txt\""));
+        assert!(!day.contains("{{"));
+        assert_eq!(
+            fuzz_target(13),
+            include_str!("../fuzz/fuzz_targets/day13.rs").replace("\r\n", "\n")
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, ParseError,
+};
+use std::{any::Any, fmt};
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Answer {
+    Integer(i64),
+    String(String),
+    /// Multi-line output that has to be read by a human, e.g. the CRT of day 10.
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::String(s) | Answer::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(i64::try_from(n).expect("answer does not fit into an i64"))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u32, u64, usize, i64);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::String(s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.write_str("1"),
+            Part::Two => f.write_str("2"),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = u8;
+
+    fn try_from(part: u8) -> Result<Self, Self::Error> {
+        match part {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(part),
+        }
+    }
+}
+
+/// A day's puzzle, from raw input to both answers.
+///
+/// A part returns `None` if the input has no answer, e.g. there is no path
+/// up the hill of day 12.
+pub trait Solution {
+    const DAY: u8;
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Option<Answer>;
+    fn part2(input: &Self::Input) -> Option<Answer>;
+}
+
+/// Parsed input of a [`Day`], opaque to everyone but the day itself.
+pub struct Parsed(Box<dyn Any + Send + Sync>);
+
+/// A type erased [`Solution`], so days can be iterated over.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    parse: fn(&str) -> Result<Parsed, ParseError>,
+    part1: fn(&Parsed) -> Option<Answer>,
+    part2: fn(&Parsed) -> Option<Answer>,
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Day").field("day", &self.day).finish()
+    }
+}
+
+impl Day {
+    pub const fn new<S>() -> Self
+    where
+        S: Solution,
+        S::Input: Send + Sync + 'static,
+    {
+        Self {
+            day: S::DAY,
+            parse: |input| S::parse(input).map(|input| Parsed(Box::new(input))),
+            part1: |parsed| S::part1(Self::downcast::<S>(parsed)),
+            part2: |parsed| S::part2(Self::downcast::<S>(parsed)),
+        }
+    }
+
+    fn downcast<S>(parsed: &Parsed) -> &S::Input
+    where
+        S: Solution,
+        S::Input: 'static,
+    {
+        parsed
+            .0
+            .downcast_ref()
+            .expect("input was parsed by a different day")
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        (self.parse)(input)
+    }
+
+    /// Solves `part` for input that was parsed by this day.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `parsed` was produced by a different day.
+    pub fn solve(&self, parsed: &Parsed, part: Part) -> Option<Answer> {
+        match part {
+            Part::One => (self.part1)(parsed),
+            Part::Two => (self.part2)(parsed),
+        }
+    }
+
+    /// Parses `input` and solves `part` in one go.
+    pub fn run(&self, input: &str, part: Part) -> Result<Option<Answer>, ParseError> {
+        Ok(self.solve(&self.parse(input)?, part))
+    }
+}
+
+/// All days that have a solution, in order.
+pub static DAYS: [Day; 18] = [
+    Day::new::<day01::Day01>(),
+    Day::new::<day02::Day02>(),
+    Day::new::<day03::Day03>(),
+    Day::new::<day04::Day04>(),
+    Day::new::<day05::Day05>(),
+    Day::new::<day06::Day06>(),
+    Day::new::<day07::Day07>(),
+    Day::new::<day08::Day08>(),
+    Day::new::<day09::Day09>(),
+    Day::new::<day10::Day10>(),
+    Day::new::<day11::Day11>(),
+    Day::new::<day12::Day12>(),
+    Day::new::<day13::Day13>(),
+    Day::new::<day14::Day14>(),
+    Day::new::<day15::Day15>(),
+    Day::new::<day16::Day16>(),
+    Day::new::<day17::Day17>(),
+    Day::new::<day18::Day18>(),
+];
+
+/// Looks up a day in [`DAYS`].
+pub fn day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{day, Answer, Part, DAYS};
+
+    #[test]
+    fn test_registry() {
+        for (i, d) in DAYS.iter().enumerate() {
+            assert_eq!(usize::from(d.day), i + 1);
+        }
+        assert!(day(0).is_none());
+    }
+
+    #[test]
+    fn test_run() -> anyhow::Result<()> {
+        let day04 = day(4).unwrap();
+        let answer = day04.run(
+            "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8",
+            Part::Two,
+        )?;
+        assert_eq!(answer, Some(Answer::Integer(4)));
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "different day")]
+    fn test_wrong_day() {
+        let parsed = day(4).unwrap().parse("2-4,6-8").unwrap();
+        day(9).unwrap().solve(&parsed, Part::One);
+    }
+}
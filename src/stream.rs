@@ -0,0 +1,54 @@
+//! Solvers for the days that only need a single pass over their input.
+//!
+//! They read from any [`BufRead`] instead of a `&str`, so a huge generated
+//! input is never held in memory at once. Days 1 and 10 need constant memory
+//! and day 6 only keeps a window of the last 14 characters. Day 9 still has
+//! to remember every position the tails visited, which grows with the
+//! input.
+
+use crate::solution::Answer;
+use crate::{day01, day06, day09, day10};
+use std::io::BufRead;
+
+pub use day01::solve_stream as day01;
+pub use day06::solve_stream as day06;
+pub use day09::solve_stream as day09;
+pub use day10::solve_stream as day10;
+
+/// Days that have a streaming solver.
+pub const DAYS: [u8; 4] = [1, 6, 9, 10];
+
+/// Solves both parts of `day` from `reader`, `None` if the day has no
+/// streaming solver.
+pub fn solve<R: BufRead>(day: u8, reader: R) -> Option<anyhow::Result<[Option<Answer>; 2]>> {
+    let answers = match day {
+        1 => day01(reader).map(|(a, b)| [Some(a.into()), Some(b.into())]),
+        6 => day06(reader).map(|(a, b)| [a.map(Answer::from), b.map(Answer::from)]),
+        9 => day09(reader).map(|(a, b)| [Some(a.into()), Some(b.into())]),
+        10 => day10(reader).map(|(a, b)| [Some(a.into()), Some(Answer::Text(b))]),
+        _ => return None,
+    };
+    Some(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{solve, DAYS};
+    use crate::{gen, solution};
+
+    #[test]
+    fn test_matches_solution() -> anyhow::Result<()> {
+        for day in DAYS {
+            let input = gen::generate(day, 7, 30).unwrap().input;
+            let expected = solution::Part::ALL
+                .map(|part| solution::day(day).unwrap().run(&input, part).unwrap());
+            assert_eq!(
+                solve(day, input.as_bytes()).unwrap()?,
+                expected,
+                "day {day}"
+            );
+        }
+        assert!(solve(2, &b""[..]).is_none());
+        Ok(())
+    }
+}
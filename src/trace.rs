@@ -0,0 +1,97 @@
+//! Execution traces of the simulations.
+//!
+//! With the `trace` feature the simulating days emit `tracing` spans and
+//! events at `TRACE` level for every step: knot positions of day 9, register
+//! values of day 10, item throws of day 11 and crate moves of day 5. Without
+//! the feature `event!` and `span!` expand to nothing, so the solvers pay
+//! nothing for them.
+
+/// Emits a `TRACE` event, takes the same arguments as [`tracing::trace!`].
+macro_rules! event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "trace")]
+        ::tracing::trace!($($arg)*);
+    };
+}
+
+/// Enters a `TRACE` span until the returned guard is dropped, takes the same
+/// arguments as [`tracing::trace_span!`].
+macro_rules! span {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "trace")]
+        let guard = ::tracing::trace_span!($($arg)*).entered();
+        #[cfg(not(feature = "trace"))]
+        let guard = $crate::trace::NoSpan;
+        guard
+    }};
+}
+
+pub(crate) use event;
+pub(crate) use span;
+
+/// Stands in for an entered span without the `trace` feature.
+#[cfg(not(feature = "trace"))]
+pub(crate) struct NoSpan;
+
+/// Writes every span and event, one JSON object per line, to the file at
+/// `path` for the rest of the process.
+#[cfg(feature = "trace")]
+pub fn to_file(path: &std::path::Path) -> anyhow::Result<()> {
+    let file = std::fs::File::create(path)
+        .map_err(|err| anyhow::anyhow!("cannot create {}: {err}", path.display()))?;
+    tracing_subscriber::fmt()
+        .json()
+        .with_max_level(tracing::Level::TRACE)
+        .with_span_list(true)
+        .with_writer(std::sync::Mutex::new(file))
+        .try_init()
+        .map_err(|err| anyhow::anyhow!("cannot install the tracing subscriber: {err}"))
+}
+
+#[cfg(all(test, feature = "trace"))]
+mod tests {
+    use crate::solution::{self, Part};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_trace() -> anyhow::Result<()> {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .json()
+            .with_max_level(tracing::Level::TRACE)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        let day = solution::day(9).unwrap();
+        tracing::subscriber::with_default(subscriber, || -> anyhow::Result<()> {
+            let moves = day.parse("R 4\nU 4\nL 3")?;
+            day.solve(&moves, Part::One);
+            Ok(())
+        })?;
+
+        let trace = String::from_utf8(buffer.0.lock().unwrap().clone())?;
+        let steps = trace
+            .lines()
+            .map(serde_json::from_str::<serde_json::Value>)
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(steps.len(), 11);
+        assert_eq!(steps[3]["fields"]["head"], "(4, 0)");
+        assert_eq!(steps[3]["fields"]["tail"], "(3, 0)");
+        assert_eq!(steps[3]["span"]["dir"], "Right");
+        Ok(())
+    }
+}
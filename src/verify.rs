@@ -0,0 +1,203 @@
+use crate::{
+    input::normalize,
+    solution::{Answer, Day, Part},
+    ParseError,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, path::Path};
+
+/// Identifies a puzzle input independent of line endings and trailing
+/// whitespace.
+///
+/// This is the 64 bit FNV-1a hash of the normalized input, which unlike
+/// `DefaultHasher` is guaranteed to stay the same across Rust releases.
+pub fn input_hash(input: &str) -> String {
+    let hash = normalize(input)
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+/// A known-correct answer for one part of a specific input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub expected: String,
+}
+
+/// The contents of `answers.toml`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnswerDb {
+    #[serde(default, rename = "answer")]
+    pub answers: Vec<Entry>,
+}
+
+impl AnswerDb {
+    /// Loads the database at `path`, a missing file is an empty database.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content)
+                .map_err(|err| anyhow::anyhow!("invalid {}: {err}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(anyhow::anyhow!("cannot read {}: {err}", path.display())),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        std::fs::write(path, toml::to_string(self)?)
+            .map_err(|err| anyhow::anyhow!("cannot write {}: {err}", path.display()))
+    }
+
+    pub fn expected(&self, day: u8, part: Part, hash: &str) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|e| e.day == day && e.part == u8::from(part) && e.input == hash)
+            .map(|e| e.expected.as_str())
+    }
+
+    /// Adds or replaces the expected answer for `part` of `day`.
+    pub fn record(&mut self, day: u8, part: Part, hash: &str, answer: &Answer) {
+        let entry = Entry {
+            day,
+            part: part.into(),
+            input: hash.to_owned(),
+            expected: answer.to_string(),
+        };
+        match self
+            .answers
+            .iter_mut()
+            .find(|e| e.day == day && e.part == entry.part && e.input == hash)
+        {
+            Some(e) => *e = entry,
+            None => self.answers.push(entry),
+        }
+        self.answers.sort_by_key(|e| (e.day, e.part));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Match,
+    Mismatch {
+        expected: String,
+        actual: Option<Answer>,
+    },
+    /// The input is not in the database, so there is nothing to compare to.
+    Unknown {
+        actual: Option<Answer>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub outcome: Outcome,
+}
+
+/// Solves both parts of `day` and compares them to the database.
+pub fn verify(db: &AnswerDb, day: &Day, input: &str) -> Result<Vec<Report>, ParseError> {
+    let hash = input_hash(input);
+    let parsed = day.parse(input)?;
+
+    Ok(Part::ALL
+        .into_iter()
+        .map(|part| {
+            let actual = day.solve(&parsed, part);
+            let outcome = match db.expected(day.day, part, &hash) {
+                None => Outcome::Unknown { actual },
+                Some(expected)
+                    if actual.as_ref().map(Answer::to_string).as_deref() == Some(expected) =>
+                {
+                    Outcome::Match
+                }
+                Some(expected) => Outcome::Mismatch {
+                    expected: expected.to_owned(),
+                    actual,
+                },
+            };
+            Report {
+                day: day.day,
+                part,
+                input: hash.clone(),
+                outcome,
+            }
+        })
+        .collect())
+}
+
+/// Line by line diff of an expected and an actual answer.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut diff = String::new();
+
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => return diff,
+            (Some(e), Some(a)) if e == a => writeln!(diff, "  {e}").unwrap(),
+            (e, a) => {
+                if let Some(e) = e {
+                    writeln!(diff, "- {e}").unwrap();
+                }
+                if let Some(a) = a {
+                    writeln!(diff, "+ {a}").unwrap();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, input_hash, verify, AnswerDb, Outcome};
+    use crate::solution::{self, Answer, Part};
+
+    const INPUT: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8";
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(INPUT), input_hash(&INPUT.replace('\n', "\r\n")));
+        assert_ne!(input_hash(INPUT), input_hash("2-4,6-8"));
+    }
+
+    #[test]
+    fn test_verify() -> anyhow::Result<()> {
+        let day = solution::day(4).unwrap();
+        let hash = input_hash(INPUT);
+        let mut db = AnswerDb::default();
+        db.record(4, Part::One, &hash, &Answer::Integer(2));
+        db.record(4, Part::Two, &hash, &Answer::Integer(5));
+
+        let db: AnswerDb = toml::from_str(&toml::to_string(&db)?)?;
+        let outcomes: Vec<_> = verify(&db, day, INPUT)?
+            .into_iter()
+            .map(|r| r.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            [
+                Outcome::Match,
+                Outcome::Mismatch {
+                    expected: "5".into(),
+                    actual: Some(Answer::Integer(4)),
+                }
+            ]
+        );
+
+        let report = verify(&db, day, "2-4,6-8")?;
+        assert!(matches!(report[0].outcome, Outcome::Unknown { .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(diff("#.\n.#\n", "#.\n##\n"), "  #.\n- .#\n+ ##\n");
+        assert_eq!(diff("1", "1\n2"), "  1\n+ 2\n");
+    }
+}
@@ -0,0 +1,132 @@
+//! Frame by frame animations of the simulating days.
+//!
+//! Each animated day turns its parsed input into an iterator of text frames,
+//! which are either played in the terminal with ANSI escapes or dumped to
+//! numbered text files.
+
+use crate::solution::Part;
+use crate::{day05, day09, day10, day12, day14, ParseError};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Days that can be animated.
+pub const DAYS: [u8; 5] = [5, 9, 10, 12, 14];
+
+/// A lazily rendered animation, frames are computed as they are shown.
+pub type Frames = Box<dyn Iterator<Item = String>>;
+
+/// The frames of `part` of `day` for `input`, `None` if the day has no
+/// animation.
+pub fn frames(day: u8, part: Part, input: &str) -> Option<Result<Frames, ParseError>> {
+    let frames = match day {
+        5 => day05::input_gen(input).map(|p| Box::new(day05::frames(p, part)) as Frames),
+        9 => day09::input_gen(input).map(|moves| day09::frames(moves, part)),
+        10 => day10::input_gen(input).map(|p| Box::new(day10::frames(p)) as Frames),
+        12 => day12::input_gen(input).map(|map| Box::new(day12::frames(map, part)) as Frames),
+        14 => day14::input_gen(input).map(|cave| Box::new(day14::frames(cave, part)) as Frames),
+        _ => return None,
+    };
+    Some(frames)
+}
+
+/// Shows `frames` one after the other at the top of a cleared terminal,
+/// waiting `delay` between them, and returns the number of frames shown.
+pub fn play<W: Write>(
+    mut frames: impl Iterator<Item = String>,
+    delay: Duration,
+    out: &mut W,
+) -> io::Result<usize> {
+    // hide the cursor while playing
+    write!(out, "\x1b[?25l")?;
+    let mut count = 0;
+    let result = frames.try_for_each(|frame| {
+        // move to the top left and clear everything below
+        write!(out, "\x1b[H\x1b[J{frame}")?;
+        out.flush()?;
+        count += 1;
+        std::thread::sleep(delay);
+        Ok(())
+    });
+    write!(out, "\x1b[?25h")?;
+    out.flush()?;
+    result.map(|()| count)
+}
+
+/// Writes every frame to `dir` as `frame_00000.txt`, `frame_00001.txt`, ...
+/// and returns the number of frames written.
+pub fn dump(frames: impl Iterator<Item = String>, dir: &Path) -> io::Result<usize> {
+    std::fs::create_dir_all(dir)?;
+    let mut count = 0;
+    for (i, frame) in frames.enumerate() {
+        std::fs::write(dir.join(format!("frame_{i:05}.txt")), frame)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dump, frames, play, DAYS};
+    use crate::solution::Part;
+    use std::time::Duration;
+
+    #[test]
+    fn test_dump() -> anyhow::Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc-2022-viz-{}", std::process::id()));
+        let input = include_str!("../fixtures/day05/example_1.txt");
+        let count = dump(frames(5, Part::One, input).unwrap()?, &dir)?;
+        assert_eq!(count, 5);
+        assert_eq!(
+            std::fs::read_to_string(dir.join("frame_00001.txt"))?,
+            "move 1 from 2 to 1\n\n[D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("frame_00004.txt"))?,
+            "move 1 from 1 to 2\n\n        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n 1   2   3\n"
+        );
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_frames() -> anyhow::Result<()> {
+        let rope = include_str!("../fixtures/day09/example_1.txt");
+        let last = frames(9, Part::One, rope).unwrap()?.last().unwrap();
+        let lines = last.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "R 2 (2/2)");
+        assert_eq!(&lines[10][20..26], ".TH##.");
+        assert_eq!(&lines[12][20..24], "s###");
+        assert_eq!(frames(9, Part::Two, rope).unwrap()?.count(), 25);
+
+        let program = include_str!("../fixtures/day10/example_1.txt");
+        let last = frames(10, Part::Two, program).unwrap()?.last().unwrap();
+        assert!(last.starts_with("cycle 240, X = 17\n"));
+        assert!(last.ends_with("#######.......#######.......#######.....\n"));
+
+        let map = include_str!("../fixtures/day12/example_1.txt");
+        let steps = frames(12, Part::One, map).unwrap()?.count();
+        assert_eq!(steps, 32);
+        let steps = frames(12, Part::Two, map).unwrap()?.count();
+        assert_eq!(steps, 30);
+
+        let cave = include_str!("../fixtures/day14/example_1.txt");
+        assert_eq!(frames(14, Part::One, cave).unwrap()?.count(), 24);
+        let last = frames(14, Part::Two, cave).unwrap()?.last().unwrap();
+        assert!(last.starts_with("grain 93 rests at 500,0\n\n"));
+
+        for day in DAYS {
+            let input = crate::gen::generate(day, 3, 10).unwrap().input;
+            let mut out = vec![];
+            let shown = play(
+                frames(day, Part::Two, &input).unwrap()?,
+                Duration::ZERO,
+                &mut out,
+            )?;
+            assert!(shown > 1, "day {day}");
+            assert!(out.starts_with(b"\x1b[?25l\x1b[H\x1b[J"));
+        }
+        assert!(frames(1, Part::One, "1").is_none());
+        Ok(())
+    }
+}
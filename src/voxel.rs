@@ -0,0 +1,225 @@
+use std::collections::{HashSet, VecDeque};
+
+/// A unit cube in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Voxel {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Voxel {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn offset(self, (dx, dy, dz): (i32, i32, i32)) -> Self {
+        Self::new(self.x + dx, self.y + dy, self.z + dz)
+    }
+
+    /// The six voxels sharing a face with this one.
+    pub fn neighbours(self) -> impl Iterator<Item = Voxel> {
+        FACES.into_iter().map(move |step| self.offset(step))
+    }
+}
+
+/// Steps to the six voxels sharing a face.
+pub const FACES: [(i32, i32, i32); 6] = [
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+/// An axis aligned box, both corners included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Voxel,
+    pub max: Voxel,
+}
+
+impl Bounds {
+    pub fn contains(&self, v: Voxel) -> bool {
+        (self.min.x..=self.max.x).contains(&v.x)
+            && (self.min.y..=self.max.y).contains(&v.y)
+            && (self.min.z..=self.max.z).contains(&v.z)
+    }
+
+    /// The smallest box holding this one and `v`.
+    pub fn include(self, v: Voxel) -> Self {
+        Self {
+            min: Voxel::new(
+                self.min.x.min(v.x),
+                self.min.y.min(v.y),
+                self.min.z.min(v.z),
+            ),
+            max: Voxel::new(
+                self.max.x.max(v.x),
+                self.max.y.max(v.y),
+                self.max.z.max(v.z),
+            ),
+        }
+    }
+
+    /// Number of voxels in the box.
+    pub fn volume(&self) -> u64 {
+        [
+            (self.min.x, self.max.x),
+            (self.min.y, self.max.y),
+            (self.min.z, self.max.z),
+        ]
+        .into_iter()
+        .map(|(min, max)| u64::from(max.abs_diff(min)) + 1)
+        .product()
+    }
+
+    /// The box grown by `n` in every direction.
+    pub fn grow(self, n: i32) -> Self {
+        Self {
+            min: self.min.offset((-n, -n, -n)),
+            max: self.max.offset((n, n, n)),
+        }
+    }
+}
+
+/// A sparse set of voxels.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoxelSet {
+    voxels: HashSet<Voxel>,
+}
+
+impl VoxelSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `v`, returns whether it was new.
+    pub fn insert(&mut self, v: Voxel) -> bool {
+        self.voxels.insert(v)
+    }
+
+    pub fn contains(&self, v: Voxel) -> bool {
+        self.voxels.contains(&v)
+    }
+
+    pub fn len(&self) -> usize {
+        self.voxels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty()
+    }
+
+    /// The voxels in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = Voxel> + '_ {
+        self.voxels.iter().copied()
+    }
+
+    /// The neighbours of `v` that are in the set.
+    pub fn neighbours(&self, v: Voxel) -> impl Iterator<Item = Voxel> + '_ {
+        v.neighbours().filter(|&n| self.contains(n))
+    }
+
+    /// The smallest box holding every voxel, `None` for an empty set.
+    pub fn bounding_box(&self) -> Option<Bounds> {
+        let mut voxels = self.iter();
+        let first = voxels.next()?;
+        let first = Bounds {
+            min: first,
+            max: first,
+        };
+        Some(voxels.fold(first, Bounds::include))
+    }
+
+    /// Number of faces not shared with another voxel of the set.
+    pub fn surface_area(&self) -> usize {
+        self.iter()
+            .map(|v| v.neighbours().filter(|&n| !self.contains(n)).count())
+            .sum()
+    }
+
+    /// The groups of voxels connected through shared faces.
+    pub fn components(&self) -> Vec<VoxelSet> {
+        let mut seen = HashSet::new();
+        let mut components = vec![];
+        for start in self.iter() {
+            if !seen.insert(start) {
+                continue;
+            }
+            let mut component = VoxelSet::new();
+            let mut queue = VecDeque::from([start]);
+            while let Some(v) = queue.pop_front() {
+                component.insert(v);
+                for n in self.neighbours(v) {
+                    if seen.insert(n) {
+                        queue.push_back(n);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+}
+
+impl FromIterator<Voxel> for VoxelSet {
+    fn from_iter<I: IntoIterator<Item = Voxel>>(iter: I) -> Self {
+        Self {
+            voxels: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bounds, Voxel, VoxelSet};
+
+    fn line(len: i32) -> impl Iterator<Item = Voxel> {
+        (0..len).map(|x| Voxel::new(x, 0, 0))
+    }
+
+    #[test]
+    fn test_surface_area() {
+        assert_eq!(VoxelSet::new().surface_area(), 0);
+        assert_eq!(line(1).collect::<VoxelSet>().surface_area(), 6);
+        assert_eq!(line(3).collect::<VoxelSet>().surface_area(), 14);
+        let set = line(3).collect::<VoxelSet>();
+        assert_eq!(set.neighbours(Voxel::new(1, 0, 0)).count(), 2);
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        assert_eq!(VoxelSet::new().bounding_box(), None);
+        let set = VoxelSet::from_iter([Voxel::new(1, -2, 3), Voxel::new(-1, 5, 0)]);
+        let bounds = set.bounding_box().unwrap();
+        assert_eq!(
+            bounds,
+            Bounds {
+                min: Voxel::new(-1, -2, 0),
+                max: Voxel::new(1, 5, 3)
+            }
+        );
+        assert!(bounds.contains(Voxel::new(0, 0, 0)));
+        assert!(!bounds.contains(Voxel::new(0, 6, 0)));
+        assert!(bounds.grow(1).contains(Voxel::new(0, 6, 0)));
+        assert_eq!(bounds.volume(), 3 * 8 * 4);
+        assert_eq!(bounds.include(Voxel::new(2, 0, 0)).volume(), 4 * 8 * 4);
+    }
+
+    #[test]
+    fn test_components() {
+        // touching edges or corners doesn't connect
+        let mut set = line(3).collect::<VoxelSet>();
+        set.insert(Voxel::new(3, 1, 0));
+        set.insert(Voxel::new(4, 2, 1));
+        let mut sizes = set
+            .components()
+            .iter()
+            .map(VoxelSet::len)
+            .collect::<Vec<_>>();
+        sizes.sort_unstable();
+        assert_eq!(sizes, [1, 1, 3]);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::error::ParseError;
+use crate::input::normalize;
+use crate::solution::{Answer, Solution};
+use aoc_runner_derive::{aoc, aoc_generator};
+
+#[aoc_generator(day{{DAY}})]
+pub fn input_gen(input: &str) -> Result<Vec<String>, ParseError> {
+    let input = normalize(input);
+    Ok(input.lines().map(str::to_owned).collect())
+}
+
+#[aoc(day{{DAY}}, part1)]
+pub fn solve_part1(_input: &[String]) -> Option<u64> {
+    None
+}
+
+#[aoc(day{{DAY}}, part2)]
+pub fn solve_part2(_input: &[String]) -> Option<u64> {
+    None
+}
+
+pub struct Day{{NN}};
+
+impl Solution for Day{{NN}} {
+    const DAY: u8 = {{DAY}};
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input_gen(input)
+    }
+
+    fn part1(input: &Self::Input) -> Option<Answer> {
+        solve_part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input) -> Option<Answer> {
+        solve_part2(input).map(Answer::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{input_gen, solve_part1, solve_part2};
+    use crate::input::windows_style;
+
+    const INPUT: &str = include_str!("../fixtures/day{{NN}}/example_1.txt");
+
+    #[test]
+    #[ignore = "fill in the answer to the example"]
+    fn test_part1() -> anyhow::Result<()> {
+        assert_eq!(solve_part1(&input_gen(INPUT)?), Some(0));
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "fill in the answer to the example"]
+    fn test_part2() -> anyhow::Result<()> {
+        assert_eq!(solve_part2(&input_gen(INPUT)?), Some(0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_crlf() -> anyhow::Result<()> {
+        let parsed = input_gen(&windows_style(INPUT))?;
+        assert_eq!(solve_part1(&parsed), solve_part1(&input_gen(INPUT)?));
+        assert_eq!(solve_part2(&parsed), solve_part2(&input_gen(INPUT)?));
+        Ok(())
+    }
+}